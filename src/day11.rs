@@ -1,6 +1,6 @@
 use std::ops::{Add, Mul};
 use regex::Regex;
-use crate::utils::get_input;
+use crate::utils::{find_cycle_hashed, get_input};
 
 pub fn part1() -> usize {
     execute(20, 3)
@@ -11,32 +11,73 @@ pub fn part2() -> usize {
 }
 
 pub fn execute(num_rounds: usize, divisor: i64) -> usize {
-    let mut monkeys = get_monkeys();
-    let lcd: i64 = monkeys.iter().map(|m| m.divisible_by_test).product();
+    let monkeys = get_monkeys(&get_input(11));
+    let mut inspection_counts = count_inspections(&monkeys, num_rounds, divisor);
+
+    inspection_counts.sort();
+    inspection_counts.reverse();
+    inspection_counts[0] * inspection_counts[1]
+}
 
-    for _ in 0..num_rounds {
-        for i in 0..monkeys.len() {
-            monkeys[i].inspection_count += monkeys[i].items.len();
-
-            for item in monkeys[i].items.clone() {
-                let item = monkeys[i].get_new_worry_level(item) / divisor;
-                let new_index = if (item % monkeys[i].divisible_by_test) == 0 {
-                    monkeys[i].monkey_if_true
-                } else {
-                    monkeys[i].monkey_if_false
-                };
-                
-                monkeys[new_index].items.push(item % lcd);
+// Items never affect each other, so each one is followed on its own. The state of
+// every item together takes far too long to repeat, but a single item's monkey
+// and worry level repeat within a few hundred rounds, after which its
+// inspections grow by the same amount every cycle.
+fn count_inspections(monkeys: &[Monkey], num_rounds: usize, divisor: i64) -> Vec<usize> {
+    let lcd: i64 = monkeys.iter().map(|m| m.divisible_by_test).product();
+    let mut inspection_counts = vec![0; monkeys.len()];
+
+    for (index, monkey) in monkeys.iter().enumerate() {
+        for item in monkey.items.iter() {
+            let initial = (index, *item);
+            let cycle = find_cycle_hashed(initial, |state| do_round(monkeys, *state, divisor, lcd, &mut vec![]));
+
+            // totals[i] holds this item's inspections per monkey after i rounds
+            let mut totals = vec![vec![0; monkeys.len()]];
+            let mut state = initial;
+            for _ in 0..cycle.start + cycle.length {
+                let mut inspected_by = vec![];
+                state = do_round(monkeys, state, divisor, lcd, &mut inspected_by);
+
+                let mut counts = totals.last().unwrap().clone();
+                for i in inspected_by {
+                    counts[i] += 1;
+                }
+                totals.push(counts);
             }
 
-            monkeys[i].items.clear();
+            for (i, count) in inspection_counts.iter_mut().enumerate() {
+                *count += cycle.extrapolate(num_rounds, |round| totals[round][i]);
+            }
         }
     }
 
-    let mut inspection_counts = monkeys.iter().map(|m| m.inspection_count).collect::<Vec<_>>();
-    inspection_counts.sort();
-    inspection_counts.reverse();
-    inspection_counts[0] * inspection_counts[1]
+    inspection_counts
+}
+
+/// Moves a single item through one round, starting at the given monkey. Returns
+/// the monkey holding the item at the start of the next round along with its
+/// worry level, and records every monkey that inspected it.
+fn do_round(monkeys: &[Monkey], (mut index, mut item): (usize, i64), divisor: i64, lcd: i64, inspected_by: &mut Vec<usize>) -> (usize, i64) {
+    loop {
+        let monkey = &monkeys[index];
+        inspected_by.push(index);
+
+        item = monkey.get_new_worry_level(item) / divisor;
+        let new_index = if (item % monkey.divisible_by_test) == 0 {
+            monkey.monkey_if_true
+        } else {
+            monkey.monkey_if_false
+        };
+        item %= lcd;
+
+        // Monkeys take turns in order, so an item thrown to a later monkey is
+        // inspected again in the same round
+        if new_index <= index {
+            return (new_index, item);
+        }
+        index = new_index;
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -48,7 +89,6 @@ struct Monkey {
     divisible_by_test: i64,
     monkey_if_true: usize,
     monkey_if_false: usize,
-    inspection_count: usize,
 }
 
 impl Monkey {
@@ -73,7 +113,7 @@ enum Arg {
     Const(i64),
 }
 
-fn get_monkeys() -> Vec<Monkey> {
+fn get_monkeys(input: &str) -> Vec<Monkey> {
     let mut monkeys = vec![];
 
     let regex = Regex::new(
@@ -84,7 +124,7 @@ r"Monkey \d+:
     If true: throw to monkey (?P<if_true>\d+)
     If false: throw to monkey (?P<if_false>\d+)").unwrap();
     
-    for capture in regex.captures_iter(&get_input(11)[..]) {
+    for capture in regex.captures_iter(input) {
         let lhs = match capture.name("lhs").unwrap().as_str() {
             "old" => Arg::Old,
             num => Arg::Const(num.parse().unwrap()),
@@ -109,9 +149,84 @@ r"Monkey \d+:
             divisible_by_test: capture.name("divisible").unwrap().as_str().parse().unwrap(),
             monkey_if_true: capture.name("if_true").unwrap().as_str().parse().unwrap(),
             monkey_if_false: capture.name("if_false").unwrap().as_str().parse().unwrap(),
-            inspection_count: 0,
         });
     }
 
     monkeys
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    // Simulates every round with all of the items at once
+    fn brute_force(monkeys: &mut [Monkey], num_rounds: usize, divisor: i64) -> Vec<usize> {
+        let lcd: i64 = monkeys.iter().map(|m| m.divisible_by_test).product();
+        let mut inspection_counts = vec![0; monkeys.len()];
+
+        for _ in 0..num_rounds {
+            for i in 0..monkeys.len() {
+                inspection_counts[i] += monkeys[i].items.len();
+
+                for item in monkeys[i].items.clone() {
+                    let item = monkeys[i].get_new_worry_level(item) / divisor;
+                    let new_index = if (item % monkeys[i].divisible_by_test) == 0 {
+                        monkeys[i].monkey_if_true
+                    } else {
+                        monkeys[i].monkey_if_false
+                    };
+
+                    monkeys[new_index].items.push(item % lcd);
+                }
+
+                monkeys[i].items.clear();
+            }
+        }
+
+        inspection_counts
+    }
+
+    #[test]
+    fn test_count_inspections() {
+        let monkeys = get_monkeys(EXAMPLE);
+
+        assert_eq!(count_inspections(&monkeys, 20, 3), vec![101, 95, 7, 105]);
+        assert_eq!(count_inspections(&monkeys, 10_000, 1), vec![52166, 47830, 1938, 52013]);
+
+        for (num_rounds, divisor) in [(1, 3), (20, 3), (1000, 1), (10_000, 1)] {
+            assert_eq!(count_inspections(&monkeys, num_rounds, divisor), brute_force(&mut monkeys.clone(), num_rounds, divisor));
+        }
+
+        let monkeys = get_monkeys(&get_input(11));
+        assert_eq!(count_inspections(&monkeys, 10_000, 1), brute_force(&mut monkeys.clone(), 10_000, 1));
+    }
+}
@@ -1,5 +1,4 @@
-use std::collections::HashSet;
-use crate::utils::{get_input, Grid, Direction};
+use crate::utils::{get_input, BitGrid, Grid, Direction};
 
 pub fn part1() -> usize {
     execute(true)
@@ -16,25 +15,25 @@ pub fn execute(is_part_1: bool) -> usize {
     let first_point = height_map.grid.at(first_point.0, first_point.1).unwrap();
 
     let mut steps = 1;
-    let mut points_seen = HashSet::new();
-    points_seen.insert(first_point);
+    let mut points_seen = BitGrid::new(height_map.grid.x_len(), height_map.grid.y_len());
+    points_seen.insert(first_point.x(), first_point.y());
 
-    let mut to_process = points_seen.clone();
+    let mut to_process = vec![first_point];
 
     loop {
         assert!(!to_process.is_empty());
 
         let copy = to_process;
-        to_process = HashSet::new();
+        to_process = vec![];
 
         for node in copy {
-            for dir in vec![Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
+            for dir in Direction::all() {
                 let new_node = node.move_in_direction(dir, 1);
                 if new_node.is_none() {
                     continue;
                 }
                 let new_node = new_node.unwrap();
-                if points_seen.contains(&new_node) {
+                if points_seen.get(new_node.x(), new_node.y()) {
                     continue;
                 }
 
@@ -49,8 +48,8 @@ pub fn execute(is_part_1: bool) -> usize {
                     return steps
                 }
 
-                to_process.insert(new_node);
-                points_seen.insert(new_node);
+                to_process.push(new_node);
+                points_seen.insert(new_node.x(), new_node.y());
             }
         }
 
@@ -1,5 +1,5 @@
 use std::collections::HashSet;
-use crate::utils::{get_input, Grid, Point, Range};
+use crate::utils::{get_input, Canvas, Grid, Point, Rect, Segment};
 
 pub fn part1() -> usize {
     let (mut grid, start) = get_grid();
@@ -26,14 +26,14 @@ pub fn part1() -> usize {
             break;
         }
 
-        grid.set_at_point(Point::new(curr_point.x() as isize, curr_point.y() as isize), Type::Sand);
+        grid.set_at_point(curr_point.point(), Type::Sand);
         iterations += 1;
     }
 }
 
 pub fn part2() -> usize {
     let (grid, start) = get_grid();
-    let max_y = grid.y_len() as isize;
+    let max_y = grid.bounds().max.y + 1;
     
     let mut queue = HashSet::new();
     queue.insert(start);
@@ -49,7 +49,7 @@ pub fn part2() -> usize {
 
                 // If the point is in the grid, we may need to skip it. If it is not in the grid, it
                 // should always be considered unless it is in the floor
-                if let Some(p) = grid.at(new_grid_point.x as usize, new_grid_point.y as usize) {
+                if let Some(p) = grid.at_point(new_grid_point) {
                     if *p != Type::Air {
                         continue;
                     }
@@ -77,57 +77,30 @@ enum Type {
 }
 
 fn get_grid() -> (Grid<Type>, Point) {
-    let mut points = HashSet::new();
-
-    let mut min_x = 500;
-    let mut max_x = 500;
-    let mut min_y = 0;
-    let mut max_y = 0;
-
-    let mut last_point: Option<Point>;
+    let start = Point::new(500, 0);
+    let mut segments = vec![];
 
     for line in get_input(14).lines() {
-        last_point = None;
+        let mut path = vec![];
 
         for part in line.split(" -> ") {
             let mut coordinates = part.split(",");
-            let x = coordinates.next().unwrap().parse::<u32>().unwrap();
-            let y = coordinates.next().unwrap().parse::<u32>().unwrap();
-
-            min_x = min_x.min(x);
-            max_x = max_x.max(x);
-            min_y = min_y.min(y);
-            max_y = max_y.max(y);
-
-            if let Some(p) = last_point {
-                for cy in Range::inclusive(p.y, y as isize) {
-                    for cx in Range::inclusive(p.x, x as isize) {
-                        points.insert(Point::new(cx, cy));
-                    }
-                }
-            }
-
-            last_point = Some(Point::new(x as isize, y as isize));
+            let x = coordinates.next().unwrap().parse::<isize>().unwrap();
+            let y = coordinates.next().unwrap().parse::<isize>().unwrap();
+            path.push(Point::new(x, y));
         }
 
-        assert!(matches!(last_point, Some(_)));
+        assert!(!path.is_empty());
+        segments.extend(Segment::path(&path));
     }
 
-    let width = max_x - min_x + 1;
-    let height = max_y - min_y + 1;
-
-    let mut grid_rows = vec![];
-    for y in 0..height {
-        let mut row = vec![];
-        for x in 0..width {
-            if points.contains(&Point::new((x + min_x) as isize, (y + min_y) as isize)) {
-                row.push(Type::Rock);
-            } else {
-                row.push(Type::Air);
-            }
-        }
-        grid_rows.push(row);
+    let bounds = segments.iter().fold(Rect::new(start, start), |bounds, segment| bounds.union(&segment.bounds()));
+    let mut grid = Grid::new(vec![vec![Type::Air; bounds.width()]; bounds.height()])
+        .with_origin(bounds.min);
+
+    for segment in segments {
+        grid.draw_line(segment.a, segment.b, Type::Rock);
     }
 
-    (Grid::new(grid_rows), Point::new(500 - min_x as isize, 0))
+    (grid, start)
 }
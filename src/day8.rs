@@ -1,20 +1,12 @@
-use crate::utils::{Grid, GridNode, get_input};
+use crate::utils::{Direction, Grid, get_input};
 
 pub fn part1() -> usize {
     let grid = get_grid();
     let mut count = 0;
 
-    for (y, row) in grid.iter().enumerate() {
-        for (x, height) in row.iter().enumerate() {
-            let node = grid.at(x, y).unwrap();
-
-            if node.left_iter().all(|n| *n < *height) ||
-                node.right_iter().all(|n| *n < *height) ||
-                node.up_iter().all(|n| *n < *height) ||
-                node.down_iter().all(|n| *n < *height)
-            {
-                count += 1;
-            }
+    for node in grid.nodes() {
+        if Direction::all().any(|dir| node.ray(dir).first_match(|n| *n >= *node).is_none()) {
+            count += 1;
         }
     }
 
@@ -25,32 +17,12 @@ pub fn part2() -> usize {
     let grid = get_grid();
     let mut max_vis = 0;
 
-    fn get_num_visible_trees<'a, I>(height: u8, iter: I) -> usize 
-        where I: Iterator<Item=GridNode<'a, u8>>
-    {
-        let mut n = 0;
-
-        for node in iter {
-            n += 1;
-            if *node >= height {
-                break;
-            }
-        }
+    for node in grid.nodes() {
+        let total_vis: usize = Direction::all()
+            .map(|dir| node.ray(dir).count_visible(|n| *n >= *node))
+            .product();
 
-        n
-    }
-
-    for (y, row) in grid.iter().enumerate() {
-        for (x, height) in row.iter().enumerate() {
-            let node = grid.at(x, y).unwrap();
-            
-            let total_vis = get_num_visible_trees(*height, node.left_iter())
-                * get_num_visible_trees(*height, node.right_iter())
-                * get_num_visible_trees(*height, node.up_iter())
-                * get_num_visible_trees(*height, node.down_iter());
-
-            max_vis = max_vis.max(total_vis);
-        }
+        max_vis = max_vis.max(total_vis);
     }
 
     max_vis
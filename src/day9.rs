@@ -1,5 +1,4 @@
-use std::collections::HashSet;
-use crate::utils::{get_input, Direction, Point};
+use crate::utils::{get_input, Direction, Point, SparseGrid};
 
 pub fn part1() -> usize {
     execute::<2>()
@@ -10,10 +9,10 @@ pub fn part2() -> usize {
 }
 
 fn execute<const N: usize>() -> usize {
-    let mut tail_positions = HashSet::new();
+    let mut tail_positions = SparseGrid::new(false);
     let mut points = [Point::new(0, 0); N];
 
-    tail_positions.insert(points[0]);
+    tail_positions.set(points[0], true);
 
     for movement in get_movements() {
         for _ in 0..movement.amount {
@@ -23,7 +22,7 @@ fn execute<const N: usize>() -> usize {
                 points[i] = move_tail(points[i - 1], points[i]);
             }
 
-            tail_positions.insert(points[points.len() - 1]);
+            tail_positions.set(points[points.len() - 1], true);
         }
     }
 
@@ -31,7 +30,7 @@ fn execute<const N: usize>() -> usize {
 }
 
 pub fn move_tail(head: Point, old_tail: Point) -> Point {
-    if ((head.x - old_tail.x) + (head.y - old_tail.y)).abs() <= 1 {
+    if head == old_tail || head.is_adjacent(old_tail) {
         return old_tail
     }
 
@@ -40,18 +39,7 @@ pub fn move_tail(head: Point, old_tail: Point) -> Point {
         return head + delta;
     }
 
-    *get_surrounding_diagonals(old_tail).iter().min_by_key(|p| {
-        (p.x - head.x).abs() + (p.y - head.y).abs()
-    }).unwrap()
-}
-
-fn get_surrounding_diagonals(point: Point) -> [Point; 4] {
-    [
-        Point::new(point.x - 1, point.y - 1),
-        Point::new(point.x - 1, point.y + 1),
-        Point::new(point.x + 1, point.y - 1),
-        Point::new(point.x + 1, point.y + 1),
-    ]
+    old_tail.diagonals().min_by_key(|p| p.manhattan(head)).unwrap()
 }
 
 #[derive(Debug)]
@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+use num::Num;
+use crate::utils::{Grid, Point, Rect};
+
+/// A summed-area table over a Grid, answering sums over any rectangle in O(1)
+/// 
+/// # Examples
+/// ```
+/// let sums = grid.prefix_sums();
+/// let total = sums.sum(Rect::new(Point::new(10, 10), Point::new(12, 12)));
+/// let everything = sums.sum(grid.bounds());
+/// ```
+pub struct PrefixSums<T> {
+    // sums[y][x] holds the sum of every element above and to the left of index
+    // (x, y), exclusive, so the first row and column are all zero
+    sums: Vec<Vec<T>>,
+    origin: Point,
+}
+
+impl<T: Num + Copy> PrefixSums<T> {
+    /// The sum of every element inside the rectangle, in world coordinates. The
+    /// rectangle must fit inside the grid. Empty rectangles sum to zero.
+    pub fn sum(&self, rect: Rect) -> T {
+        if rect.is_empty() {
+            return T::zero();
+        }
+
+        let (x, y) = (rect.min - self.origin).try_into_usize_pair().expect("Rectangle does not fit inside the grid");
+        let (width, height) = (rect.width(), rect.height());
+        assert!(y + height < self.sums.len() && x + width < self.sums[0].len(), "Rectangle does not fit inside the grid");
+
+        // Add before subtracting so that unsigned sums never go below zero part-way
+        self.sums[y + height][x + width] + self.sums[y][x] - self.sums[y][x + width] - self.sums[y + height][x]
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn prefix_sums(&self) -> PrefixSums<T> where T: Num + Copy {
+        let mut sums = vec![vec![T::zero(); self.x_len() + 1]; self.y_len() + 1];
+
+        for node in self.nodes() {
+            let (x, y) = (node.x(), node.y());
+            sums[y + 1][x + 1] = *node + sums[y][x + 1] + sums[y + 1][x] - sums[y][x];
+        }
+
+        PrefixSums { sums, origin: self.origin() }
+    }
+
+    /// The maximum of every window of `size` consecutive elements in each row. Each
+    /// row of the result has x_len - size + 1 elements.
+    pub fn sliding_max_rows(&self, size: usize) -> Grid<T> where T: PartialOrd + Copy {
+        self.sliding_rows(size, |a, b| a >= b)
+    }
+
+    pub fn sliding_min_rows(&self, size: usize) -> Grid<T> where T: PartialOrd + Copy {
+        self.sliding_rows(size, |a, b| a <= b)
+    }
+
+    /// The maximum of every window of `size` consecutive elements in each column.
+    /// Each column of the result has y_len - size + 1 elements.
+    pub fn sliding_max_columns(&self, size: usize) -> Grid<T> where T: PartialOrd + Copy {
+        self.sliding_columns(size, |a, b| a >= b)
+    }
+
+    pub fn sliding_min_columns(&self, size: usize) -> Grid<T> where T: PartialOrd + Copy {
+        self.sliding_columns(size, |a, b| a <= b)
+    }
+
+    fn sliding_rows<F: Fn(&T, &T) -> bool>(&self, size: usize, keeps: F) -> Grid<T> where T: Copy {
+        Grid::new(self.rows().map(|row| sliding_window(&row.map(|n| *n).collect::<Vec<_>>(), size, &keeps)).collect())
+    }
+
+    fn sliding_columns<F: Fn(&T, &T) -> bool>(&self, size: usize, keeps: F) -> Grid<T> where T: Copy {
+        Grid::new(self.columns().map(|column| sliding_window(&column.map(|n| *n).collect::<Vec<_>>(), size, &keeps)).collect())
+            .transpose()
+    }
+}
+
+/// Computes the best value in every window of `size` consecutive values in O(n),
+/// using a deque of indices whose values are in decreasing order of preference.
+/// `keeps(a, b)` returns true if a is at least as good as b.
+fn sliding_window<T: Copy, F: Fn(&T, &T) -> bool>(values: &[T], size: usize, keeps: F) -> Vec<T> {
+    assert!(size > 0, "Window size must be non-zero");
+
+    let mut candidates: VecDeque<usize> = VecDeque::new();
+    let mut result = vec![];
+
+    for (i, value) in values.iter().enumerate() {
+        while !candidates.is_empty() && keeps(value, &values[*candidates.back().unwrap()]) {
+            candidates.pop_back();
+        }
+        candidates.push_back(i);
+
+        if candidates[0] + size <= i {
+            candidates.pop_front();
+        }
+
+        if i + 1 >= size {
+            result.push(values[candidates[0]]);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod aggregate_tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_sums() {
+        let rect = |x0, y0, x1, y1| Rect::new(Point::new(x0, y0), Point::new(x1, y1));
+
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        let sums = grid.prefix_sums();
+
+        assert_eq!(sums.sum(grid.bounds()), 45);
+        assert_eq!(sums.sum(rect(1, 1, 2, 2)), 28);
+        assert_eq!(sums.sum(rect(2, 0, 2, 2)), 18);
+        assert_eq!(sums.sum(rect(1, 1, 0, 0)), 0);
+
+        let grid = grid.with_origin(Point::new(-1, 5));
+        assert_eq!(grid.prefix_sums().sum(rect(-1, 6, 0, 7)), 24);
+
+        let grid = Grid::new(vec![vec![0.5, 1.5]]);
+        assert_eq!(grid.prefix_sums().sum(grid.bounds()), 2.0);
+
+        let counts = Grid::new(vec![vec![1usize; 3]; 3]);
+        assert_eq!(counts.prefix_sums().sum(rect(2, 2, 2, 2)), 1);
+        assert_eq!(counts.prefix_sums().sum(rect(1, 0, 2, 2)), 6);
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let grid = Grid::new(vec![vec![1, 3, 2, 5, 4], vec![9, 7, 8, 6, 5]]);
+
+        assert_eq!(grid.sliding_max_rows(2).iter().cloned().collect::<Vec<_>>(), vec![vec![3, 3, 5, 5], vec![9, 8, 8, 6]]);
+        assert_eq!(grid.sliding_min_rows(3).iter().cloned().collect::<Vec<_>>(), vec![vec![1, 2, 2], vec![7, 6, 5]]);
+        assert_eq!(grid.sliding_max_columns(2).iter().cloned().collect::<Vec<_>>(), vec![vec![9, 7, 8, 6, 5]]);
+        assert_eq!(grid.sliding_min_columns(1).iter().cloned().collect::<Vec<_>>(), grid.iter().cloned().collect::<Vec<_>>());
+    }
+}
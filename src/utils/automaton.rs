@@ -0,0 +1,102 @@
+use std::hash::Hash;
+use crate::utils::{find_cycle_hashed, Cycle, Grid, GridNode};
+
+// Cellular automaton support. Every generation is computed from a read-only
+// snapshot of the previous one, so a rule always sees its neighbors as they were
+// before the step, regardless of the order the nodes are visited in.
+impl<T> Grid<T> {
+    /// Advances the grid by a single generation, replacing every element with the
+    /// result of the rule for its node
+    /// 
+    /// # Examples
+    /// ```
+    /// // Conway's game of life
+    /// grid.step(|node| {
+    ///     let alive = node.neighbors_diagonal().filter(|n| **n).count();
+    ///     alive == 3 || (*node && alive == 2)
+    /// });
+    /// ```
+    pub fn step<F: Fn(GridNode<T>) -> T>(&mut self, rule: F) {
+        let mut buffer = vec![];
+        self.step_into(&mut buffer, &rule);
+    }
+
+    pub fn step_n<F: Fn(GridNode<T>) -> T>(&mut self, n: usize, rule: F) {
+        let mut buffer = vec![];
+        for _ in 0..n {
+            self.step_into(&mut buffer, &rule);
+        }
+    }
+
+    /// Steps until a generation is the same as the one before it. Returns the number
+    /// of generations that changed the grid.
+    pub fn run_until_stable<F: Fn(GridNode<T>) -> T>(&mut self, rule: F) -> usize where T: PartialEq {
+        let mut buffer = vec![];
+        let mut steps = 0;
+
+        loop {
+            self.step_into(&mut buffer, &rule);
+            if self.iter().eq(buffer.iter()) {
+                return steps;
+            }
+            steps += 1;
+        }
+    }
+
+    /// Steps until the grid returns to a state it has been in before. The grid is
+    /// left in the first repeated state.
+    pub fn find_cycle<F: Fn(GridNode<T>) -> T>(&mut self, rule: F) -> Cycle where T: Clone + Eq + Hash {
+        let cycle = find_cycle_hashed(self.clone(), |grid| {
+            let mut next = grid.clone();
+            next.step(&rule);
+            next
+        });
+
+        self.step_n(cycle.start + cycle.length, rule);
+        cycle
+    }
+
+    /// Computes the next generation into buffer, then swaps it with the current
+    /// generation. The buffer is left holding the previous generation, so its
+    /// allocations can be reused by the next step.
+    fn step_into<F: Fn(GridNode<T>) -> T>(&mut self, buffer: &mut Vec<Vec<T>>, rule: &F) {
+        buffer.resize_with(self.y_len(), Vec::new);
+
+        for (row, nodes) in buffer.iter_mut().zip(self.rows()) {
+            row.clear();
+            row.extend(nodes.map(rule));
+        }
+
+        let previous = self.replace_items(std::mem::take(buffer));
+        *buffer = previous;
+    }
+}
+
+#[cfg(test)]
+mod automaton_tests {
+    use super::*;
+
+    fn life(node: GridNode<bool>) -> bool {
+        let alive = node.neighbors_diagonal().filter(|n| **n).count();
+        alive == 3 || (*node && alive == 2)
+    }
+
+    fn parse(rows: &[&str]) -> Grid<bool> {
+        Grid::new(rows.iter().map(|row| row.chars().map(|c| c == '#').collect()).collect())
+    }
+
+    #[test]
+    fn test_life() {
+        let mut blinker = parse(&[".....", "..#..", "..#..", "..#..", "....."]);
+        blinker.step(life);
+        assert_eq!(format!("{}", blinker.render()), ".....\n.....\n.###.\n.....\n.....\n");
+
+        blinker.step_n(3, life);
+        assert_eq!(format!("{}", blinker.render()), ".....\n..#..\n..#..\n..#..\n.....\n");
+        assert_eq!(blinker.find_cycle(life), Cycle { start: 0, length: 2 });
+
+        let mut block = parse(&["....", ".##.", ".#..", "...."]);
+        assert_eq!(block.run_until_stable(life), 1);
+        assert_eq!(format!("{}", block.render()), "....\n.##.\n.##.\n....\n");
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+/// Represents a 2D grid of booleans, packed into 64 bits per word
+///
+/// Intended for visited sets and masks over a Grid, where a HashSet of nodes or
+/// points would have to hash every lookup. Coordinates are the same indices as
+/// the Grid the BitGrid was sized after.
+///
+/// # Examples
+/// ```
+/// let mut visited = BitGrid::new(grid.x_len(), grid.y_len());
+///
+/// if visited.insert(node.x(), node.y()) {
+///     // First time seeing this node
+/// }
+///
+/// let both = &visited & &other_visited;
+/// println!("{} nodes were visited twice", both.count_ones());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    x_len: usize,
+    y_len: usize,
+}
+
+impl BitGrid {
+    pub fn new(x_len: usize, y_len: usize) -> Self {
+        BitGrid {
+            words: vec![0; (x_len * y_len).div_ceil(64)],
+            x_len,
+            y_len,
+        }
+    }
+
+    pub fn x_len(&self) -> usize {
+        self.x_len
+    }
+
+    pub fn y_len(&self) -> usize {
+        self.y_len
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (word, bit) = self.index(x, y);
+        self.words[word] & (1 << bit) != 0
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        let (word, bit) = self.index(x, y);
+        if value {
+            self.words[word] |= 1 << bit;
+        } else {
+            self.words[word] &= !(1 << bit);
+        }
+    }
+
+    /// Sets a cell, returning whether it was previously unset. Mirrors HashSet::insert.
+    pub fn insert(&mut self, x: usize, y: usize) -> bool {
+        let was_set = self.get(x, y);
+        self.set(x, y, true);
+        !was_set
+    }
+
+    /// The number of set cells
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Iterates over the (x, y) coordinates of every set cell in row-major order
+    pub fn iter_ones(&self) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                let index = i * 64 + bit;
+                Some((index % self.x_len, index / self.x_len))
+            })
+        })
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a | b);
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a & b);
+    }
+
+    pub fn difference_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a & !b);
+    }
+
+    pub fn symmetric_difference_with(&mut self, other: &BitGrid) {
+        self.combine_with(other, |a, b| a ^ b);
+    }
+
+    fn combine_with<F: Fn(u64, u64) -> u64>(&mut self, other: &BitGrid, f: F) {
+        assert!(self.x_len == other.x_len && self.y_len == other.y_len, "BitGrid dimensions must match");
+
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a = f(*a, *b);
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> (usize, usize) {
+        assert!(x < self.x_len && y < self.y_len);
+
+        let index = y * self.x_len + x;
+        (index / 64, index % 64)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(rhs);
+        result
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(rhs);
+        result
+    }
+}
+
+impl Sub for &BitGrid {
+    type Output = BitGrid;
+
+    fn sub(self, rhs: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.difference_with(rhs);
+        result
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, rhs: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.symmetric_difference_with(rhs);
+        result
+    }
+}
+
+impl fmt::Debug for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "BitGrid [")?;
+
+        for y in 0..self.y_len {
+            write!(f, "  ")?;
+            for x in 0..self.x_len {
+                write!(f, "{}", if self.get(x, y) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod bit_grid_tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_grid() {
+        let mut a = BitGrid::new(10, 10);
+        let mut b = BitGrid::new(10, 10);
+
+        assert!(a.insert(3, 0));
+        assert!(!a.insert(3, 0));
+        a.set(9, 9, true);
+        a.set(6, 6, true);
+        b.set(6, 6, true);
+        b.set(0, 7, true);
+
+        assert_eq!(a.count_ones(), 3);
+        assert_eq!(a.iter_ones().collect::<Vec<_>>(), vec![(3, 0), (6, 6), (9, 9)]);
+        assert_eq!((&a | &b).count_ones(), 4);
+        assert_eq!((&a & &b).iter_ones().collect::<Vec<_>>(), vec![(6, 6)]);
+        assert_eq!((&a - &b).iter_ones().collect::<Vec<_>>(), vec![(3, 0), (9, 9)]);
+        assert_eq!((&a ^ &b).count_ones(), 3);
+
+        a.set(6, 6, false);
+        assert!(!a.get(6, 6));
+    }
+}
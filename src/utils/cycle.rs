@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use num::{FromPrimitive, Num};
+
+/// A cycle found in a sequence of states x0, step(x0), step(step(x0)), ...
+/// 
+/// The states at steps `start` and `start + length` are the first pair of equal
+/// states, so the sequence repeats every `length` steps from step `start` onwards.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps a step to the earliest step with the same state
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// Extrapolates a value that grows by the same amount every cycle, such as a
+    /// running total, to step n. value_at is only called for steps before the end
+    /// of the first repetition of the cycle.
+    /// 
+    /// # Examples
+    /// ```
+    /// let heights = simulate(10_000);
+    /// let cycle = find_cycle_hashed(initial_state, step);
+    /// let height = cycle.extrapolate(1_000_000_000_000, |i| heights[i]);
+    /// ```
+    pub fn extrapolate<V, F>(&self, n: usize, value_at: F) -> V 
+        where V: Num + FromPrimitive + Copy, F: Fn(usize) -> V
+    {
+        if n < self.start {
+            return value_at(n);
+        }
+
+        let cycles = V::from_usize((n - self.start) / self.length).unwrap();
+        let growth = value_at(self.start + self.length) - value_at(self.start);
+        value_at(self.equivalent_step(n)) + cycles * growth
+    }
+}
+
+/// Finds a cycle using Floyd's tortoise and hare algorithm. Uses constant memory,
+/// but calls step roughly three times as often as find_cycle_hashed.
+pub fn find_cycle_floyd<S: Clone + PartialEq, F: Fn(&S) -> S>(initial: S, step: F) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds a cycle using Brent's algorithm. Uses constant memory and usually fewer
+/// calls to step than find_cycle_floyd.
+pub fn find_cycle_brent<S: Clone + PartialEq, F: Fn(&S) -> S>(initial: S, step: F) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds a cycle by remembering every state seen. Calls step the minimum number
+/// of times, at the cost of storing each state.
+pub fn find_cycle_hashed<S: Clone + Eq + Hash, F: Fn(&S) -> S>(initial: S, step: F) -> Cycle {
+    find_cycle_with_states(initial, step).0
+}
+
+/// Returns the state after n steps, skipping over repetitions of the cycle instead
+/// of simulating every step
+pub fn state_after<S: Clone + Eq + Hash, F: Fn(&S) -> S>(initial: S, step: F, n: usize) -> S {
+    let (cycle, mut states) = find_cycle_with_states(initial, step);
+    states.swap_remove(cycle.equivalent_step(n))
+}
+
+/// Finds a cycle, also returning every state up to the first repeated one
+fn find_cycle_with_states<S: Clone + Eq + Hash, F: Fn(&S) -> S>(initial: S, step: F) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(start) = seen.insert(state.clone(), states.len()) {
+            return (Cycle { start, length: states.len() - start }, states);
+        }
+
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod cycle_tests {
+    use super::*;
+
+    // 0, 1, ..., 10, 3, 4, ...
+    fn step(x: &usize) -> usize {
+        if *x < 10 { x + 1 } else { 3 }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let expected = Cycle { start: 3, length: 8 };
+
+        assert_eq!(find_cycle_floyd(0, step), expected);
+        assert_eq!(find_cycle_brent(0, step), expected);
+        assert_eq!(find_cycle_hashed(0, step), expected);
+        assert_eq!(find_cycle_hashed(5, step), Cycle { start: 0, length: 8 });
+
+        assert_eq!(state_after(0, step, 2), 2);
+        assert_eq!(state_after(0, step, 11), 3);
+        assert_eq!(state_after(0, step, 1_000_000_000), 3 + (1_000_000_000 - 3) % 8);
+
+        // A running total of the states, which grows by 52 every cycle
+        let totals = (0..=11).scan(0, |total, i| { *total += (0..i).fold(0, |x, _| step(&x)); Some(*total) }).collect::<Vec<_>>();
+        assert_eq!(expected.extrapolate(11, |i| totals[i]), totals[11]);
+        assert_eq!(expected.extrapolate(19, |i| totals[i]), totals[11] + 3 + 4 + 5 + 6 + 7 + 8 + 9 + 10);
+    }
+}
@@ -0,0 +1,53 @@
+// Helpers shared by the grid and point types of every dimension. Positions are
+// passed as arrays of coordinates, so the same code works for [isize; 2] and
+// [isize; 3].
+
+/// Iterates over the offsets to every neighbor of a position in N dimensions.
+/// Without diagonals these are the 2 * N offsets along a single axis, and with
+/// diagonals all 3^N - 1 offsets where each coordinate changes by at most one.
+pub fn neighbor_offsets<const N: usize>(diagonals: bool) -> impl Iterator<Item=[isize; N]> {
+    (0..3usize.pow(N as u32)).filter_map(move |mut i| {
+        let mut offset = [0; N];
+        for d in offset.iter_mut() {
+            *d = (i % 3) as isize - 1;
+            i /= 3;
+        }
+
+        let changed_axes = offset.iter().filter(|d| **d != 0).count();
+        (changed_axes == 1 || (diagonals && changed_axes > 1)).then_some(offset)
+    })
+}
+
+/// Converts a position into an index into row-major storage for a grid of the
+/// given size, where the first axis changes fastest. Returns None if the position
+/// is out of bounds.
+pub fn flat_index<const N: usize>(size: [usize; N], position: [isize; N]) -> Option<usize> {
+    let mut index = 0;
+
+    for axis in (0..N).rev() {
+        if position[axis] < 0 || position[axis] as usize >= size[axis] {
+            return None;
+        }
+        index = index * size[axis] + position[axis] as usize;
+    }
+
+    Some(index)
+}
+
+#[cfg(test)]
+mod dimensions_tests {
+    use super::*;
+
+    #[test]
+    fn test_dimensions() {
+        assert_eq!(neighbor_offsets::<2>(false).collect::<Vec<_>>(), vec![[0, -1], [-1, 0], [1, 0], [0, 1]]);
+        assert_eq!(neighbor_offsets::<2>(true).count(), 8);
+        assert_eq!(neighbor_offsets::<3>(false).count(), 6);
+        assert_eq!(neighbor_offsets::<3>(true).count(), 26);
+
+        assert_eq!(flat_index([4, 3], [1, 2]), Some(9));
+        assert_eq!(flat_index([4, 3, 2], [3, 2, 1]), Some(23));
+        assert_eq!(flat_index([4, 3], [4, 0]), None);
+        assert_eq!(flat_index([4, 3], [0, -1]), None);
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord)]
 pub enum Direction {
     Left,
@@ -7,6 +9,11 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// Iterates over the four directions clockwise, starting with Up
+    pub fn all() -> impl Iterator<Item=Direction> {
+        [Direction::Up, Direction::Right, Direction::Down, Direction::Left].into_iter()
+    }
+
     pub fn flipped(&self) -> Direction {
         match self {
             Direction::Up => Direction::Down,
@@ -29,6 +36,19 @@ impl Direction {
         self.rotated_clockwise().flipped()
     }
 
+    pub fn turn(&self, turn: Turn) -> Direction {
+        match turn {
+            Turn::Left => self.rotated_counter_clockwise(),
+            Turn::Right => self.rotated_clockwise(),
+        }
+    }
+
+    /// Wraps this direction so it displays in the given notation. Every notation
+    /// parses back with Direction::from_str.
+    pub fn notation(&self, notation: Notation) -> DirectionNotation {
+        DirectionNotation { direction: *self, notation }
+    }
+
     pub fn dx(&self) -> isize {
         match self {
             Direction::Right => 1,
@@ -46,16 +66,277 @@ impl Direction {
     }
 }
 
+impl From<Direction> for (isize, isize) {
+    fn from(direction: Direction) -> Self {
+        (direction.dx(), direction.dy())
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = ();
+
+    fn try_from(direction: Direction8) -> Result<Self, Self::Error> {
+        match direction {
+            Direction8::N => Ok(Direction::Up),
+            Direction8::E => Ok(Direction::Right),
+            Direction8::S => Ok(Direction::Down),
+            Direction8::W => Ok(Direction::Left),
+            _ => Err(()),
+        }
+    }
+}
+
 impl std::str::FromStr for Direction {
-    type Err = ();
+    type Err = ParseDirectionError;
 
+    /// Parses a direction from a word or letter ("up", "u"), an arrow ("^") or a
+    /// compass point ("north", "n"), ignoring case
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match &s.to_lowercase()[..] {
-            "u" | "up" => Ok(Direction::Up),
-            "d" | "down" => Ok(Direction::Down),
-            "l" | "left" => Ok(Direction::Left),
-            "r" | "right" => Ok(Direction::Right),
-            _ => Err(())
+            "u" | "up" | "^" | "n" | "north" => Ok(Direction::Up),
+            "d" | "down" | "v" | "s" | "south" => Ok(Direction::Down),
+            "l" | "left" | "<" | "w" | "west" => Ok(Direction::Left),
+            "r" | "right" | ">" | "e" | "east" => Ok(Direction::Right),
+            _ => Err(ParseDirectionError::new(s)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.notation(Notation::Word).fmt(f)
+    }
+}
+
+/// The ways a Direction can be written, for use with Direction::notation
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Notation {
+    /// "up", "down", "left", "right"
+    Word,
+    /// "U", "D", "L", "R"
+    Letter,
+    /// "^", "v", "<", ">"
+    Arrow,
+    /// "N", "S", "W", "E"
+    Compass,
+}
+
+/// Displays a Direction in a particular Notation. Created by Direction::notation.
+pub struct DirectionNotation {
+    direction: Direction,
+    notation: Notation,
+}
+
+impl fmt::Display for DirectionNotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let index = match self.direction {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+        };
+
+        let names = match self.notation {
+            Notation::Word => ["up", "down", "left", "right"],
+            Notation::Letter => ["U", "D", "L", "R"],
+            Notation::Arrow => ["^", "v", "<", ">"],
+            Notation::Compass => ["N", "S", "W", "E"],
+        };
+
+        f.write_str(names[index])
+    }
+}
+
+/// A turn relative to the current heading, as in "L" or "R" instructions
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl std::str::FromStr for Turn {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_lowercase()[..] {
+            "l" | "left" => Ok(Turn::Left),
+            "r" | "right" => Ok(Turn::Right),
+            _ => Err(ParseDirectionError::new(s)),
+        }
+    }
+}
+
+impl fmt::Display for Turn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Turn::Left => write!(f, "L"),
+            Turn::Right => write!(f, "R"),
+        }
+    }
+}
+
+/// Returned when a Direction or Turn can't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirectionError {
+    token: String,
+}
+
+impl ParseDirectionError {
+    fn new(token: &str) -> Self {
+        Self { token: token.to_string() }
+    }
+
+    /// The text that failed to parse
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid direction \"{}\"", self.token)
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+/// A direction on a grid where diagonal moves are allowed. Like Direction, north
+/// is towards negative y.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+// Clockwise order, so rotating by 45 degrees is a step through this array
+const DIRECTIONS_8: [Direction8; 8] = [
+    Direction8::N,
+    Direction8::NE,
+    Direction8::E,
+    Direction8::SE,
+    Direction8::S,
+    Direction8::SW,
+    Direction8::W,
+    Direction8::NW,
+];
+
+impl Direction8 {
+    /// Iterates over the eight directions clockwise, starting with N
+    pub fn all() -> impl Iterator<Item=Direction8> {
+        DIRECTIONS_8.into_iter()
+    }
+
+    /// Iterates over the four diagonal directions clockwise, starting with NE
+    pub fn diagonals() -> impl Iterator<Item=Direction8> {
+        Self::all().filter(|d| d.is_diagonal())
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.dx() != 0 && self.dy() != 0
+    }
+
+    pub fn flipped(&self) -> Direction8 {
+        self.rotated(4)
+    }
+
+    /// Rotates by 45 degrees clockwise
+    pub fn rotated_clockwise(&self) -> Direction8 {
+        self.rotated(1)
+    }
+
+    /// Rotates by 45 degrees counter-clockwise
+    pub fn rotated_counter_clockwise(&self) -> Direction8 {
+        self.rotated(7)
+    }
+
+    pub fn dx(&self) -> isize {
+        match self {
+            Direction8::NE | Direction8::E | Direction8::SE => 1,
+            Direction8::SW | Direction8::W | Direction8::NW => -1,
+            _ => 0,
         }
     }
+
+    pub fn dy(&self) -> isize {
+        match self {
+            Direction8::NW | Direction8::N | Direction8::NE => -1,
+            Direction8::SE | Direction8::S | Direction8::SW => 1,
+            _ => 0,
+        }
+    }
+
+    fn rotated(&self, steps: usize) -> Direction8 {
+        DIRECTIONS_8[(*self as usize + steps) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::N,
+            Direction::Right => Direction8::E,
+            Direction::Down => Direction8::S,
+            Direction::Left => Direction8::W,
+        }
+    }
+}
+
+impl From<Direction8> for (isize, isize) {
+    fn from(direction: Direction8) -> Self {
+        (direction.dx(), direction.dy())
+    }
+}
+
+#[cfg(test)]
+mod direction_tests {
+    use super::*;
+
+    #[test]
+    fn test_direction8() {
+        assert_eq!(Direction::all().count(), 4);
+        assert_eq!(Direction8::diagonals().collect::<Vec<_>>(), vec![Direction8::NE, Direction8::SE, Direction8::SW, Direction8::NW]);
+
+        assert_eq!(Direction8::NW.rotated_clockwise(), Direction8::N);
+        assert_eq!(Direction8::N.rotated_counter_clockwise(), Direction8::NW);
+        assert_eq!(Direction8::SW.flipped(), Direction8::NE);
+        assert_eq!(<(isize, isize)>::from(Direction8::SW), (-1, 1));
+
+        for dir in Direction::all() {
+            let dir8 = Direction8::from(dir);
+            assert_eq!((dir8.dx(), dir8.dy()), (dir.dx(), dir.dy()));
+            assert_eq!(Direction::try_from(dir8), Ok(dir));
+            assert_eq!(dir8.rotated_clockwise().rotated_clockwise(), dir.rotated_clockwise().into());
+        }
+        assert_eq!(Direction::try_from(Direction8::SE), Err(()));
+    }
+
+    #[test]
+    fn test_parse() {
+        for notation in [Notation::Word, Notation::Letter, Notation::Arrow, Notation::Compass] {
+            for dir in Direction::all() {
+                let text = dir.notation(notation).to_string();
+                assert_eq!(text.parse::<Direction>(), Ok(dir));
+            }
+        }
+
+        assert_eq!("E".parse::<Direction>(), Ok(Direction::Right));
+        assert_eq!("West".parse::<Direction>(), Ok(Direction::Left));
+        assert_eq!(Direction::Down.to_string(), "down");
+
+        let error = "x".parse::<Direction>().unwrap_err();
+        assert_eq!(error.token(), "x");
+        assert_eq!(error.to_string(), "invalid direction \"x\"");
+
+        let turn: Turn = "L".parse().unwrap();
+        assert_eq!(Direction::Up.turn(turn), Direction::Left);
+        assert_eq!(Direction::Up.turn(Turn::Right), Direction::Right);
+        assert_eq!(turn.to_string(), "L");
+        assert!("F".parse::<Turn>().is_err());
+    }
 }
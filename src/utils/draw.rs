@@ -0,0 +1,116 @@
+use crate::utils::{Grid, Point, SparseGrid};
+
+/// Drawing primitives for grids that can be written to by Point.
+///
+/// Implementors only need to provide plot. Points that fall outside of a bounded
+/// grid are silently clipped.
+///
+/// # Examples
+/// ```
+/// let mut grid = SparseGrid::new('.');
+/// grid.draw_polyline(&[Point::new(498, 4), Point::new(498, 6), Point::new(496, 6)], '#');
+/// grid.fill_rect(Point::new(0, 0), Point::new(3, 3), '~');
+/// ```
+pub trait Canvas<T: Clone> {
+    fn plot(&mut self, point: Point, value: T);
+
+    /// Draws the line between two points, including both ends. Lines that aren't
+    /// horizontal, vertical or 45 degree diagonals are rasterized with Bresenham's
+    /// algorithm.
+    fn draw_line(&mut self, a: Point, b: Point, value: T) {
+        for point in line_points(a, b) {
+            self.plot(point, value.clone());
+        }
+    }
+
+    /// Draws lines between each consecutive pair of points
+    fn draw_polyline(&mut self, points: &[Point], value: T) {
+        if let [point] = points {
+            self.plot(*point, value);
+            return;
+        }
+
+        for pair in points.windows(2) {
+            self.draw_line(pair[0], pair[1], value.clone());
+        }
+    }
+
+    /// Fills the rectangle with corners a and b, including its edges
+    fn fill_rect(&mut self, a: Point, b: Point, value: T) {
+        for y in a.y.min(b.y)..=a.y.max(b.y) {
+            for x in a.x.min(b.x)..=a.x.max(b.x) {
+                self.plot(Point::new(x, y), value.clone());
+            }
+        }
+    }
+}
+
+impl<T: Clone> Canvas<T> for Grid<T> {
+    fn plot(&mut self, point: Point, value: T) {
+        if let Some(node) = self.at_point(point) {
+            let (x, y) = (node.x(), node.y());
+            self.set(x, y, value);
+        }
+    }
+}
+
+impl<T: Clone> Canvas<T> for SparseGrid<T> {
+    fn plot(&mut self, point: Point, value: T) {
+        self.set(point, value);
+    }
+}
+
+/// Returns every point on the line between a and b, including both ends
+pub fn line_points(a: Point, b: Point) -> Vec<Point> {
+    let dx = (b.x - a.x).abs();
+    let dy = -(b.y - a.y).abs();
+    let step_x = (b.x - a.x).signum();
+    let step_y = (b.y - a.y).signum();
+
+    let mut points = vec![];
+    let mut point = a;
+    let mut error = dx + dy;
+
+    loop {
+        points.push(point);
+        if point == b {
+            return points;
+        }
+
+        let doubled_error = 2 * error;
+        if doubled_error >= dy {
+            error += dy;
+            point.x += step_x;
+        }
+        if doubled_error <= dx {
+            error += dx;
+            point.y += step_y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod draw_tests {
+    use super::*;
+
+    #[test]
+    fn test_line_points() {
+        assert_eq!(line_points(Point::new(2, 1), Point::new(2, 3)), vec![Point::new(2, 1), Point::new(2, 2), Point::new(2, 3)]);
+        assert_eq!(line_points(Point::new(0, 0), Point::new(-2, 2)), vec![Point::new(0, 0), Point::new(-1, 1), Point::new(-2, 2)]);
+        assert_eq!(line_points(Point::new(0, 0), Point::new(4, 2)).len(), 5);
+        assert_eq!(line_points(Point::new(1, 1), Point::new(1, 1)), vec![Point::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut grid = Grid::new(vec![vec!['.'; 4]; 3]);
+        grid.draw_polyline(&[Point::new(0, 0), Point::new(3, 0), Point::new(3, 5)], '#');
+        grid.fill_rect(Point::new(1, 2), Point::new(0, 1), 'o');
+
+        assert_eq!(format!("{}", grid.render()), "####\noo.#\noo.#\n");
+
+        let mut sparse = SparseGrid::new('.');
+        sparse.draw_line(Point::new(-1, -1), Point::new(1, 1), '#');
+        assert_eq!(format!("{}", sparse), "#..\n.#.\n..#\n");
+    }
+}
@@ -0,0 +1,266 @@
+use num::integer::gcd;
+use crate::utils::{Point, Rect};
+
+/// A straight line between two points, including both ends
+///
+/// Segments may have any slope. Axis-aligned segments are the common case in
+/// puzzles (rock paths, wires), but intersection works for any pair.
+///
+/// # Examples
+/// ```
+/// let a = Segment::new(Point::new(0, 0), Point::new(4, 4));
+/// let b = Segment::new(Point::new(0, 4), Point::new(4, 0));
+///
+/// assert_eq!(a.intersection(&b), Some(Intersection::Point(Point::new(2, 2))));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub a: Point,
+    pub b: Point,
+}
+
+/// Where two segments meet. See Segment::intersection.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Intersection {
+    /// The segments cross or touch at a single integer point
+    Point(Point),
+    /// The segments cross at a single point that doesn't have integer coordinates
+    Fractional(f64, f64),
+    /// The segments are collinear and share more than one point
+    Overlap(Segment),
+}
+
+impl Segment {
+    pub fn new(a: Point, b: Point) -> Self {
+        Self { a, b }
+    }
+
+    /// Returns the segments between each consecutive pair of points. A path with
+    /// a single point becomes a single zero-length segment.
+    pub fn path(points: &[Point]) -> Vec<Segment> {
+        if let [point] = points {
+            return vec![Segment::new(*point, *point)];
+        }
+
+        points.windows(2).map(|pair| Segment::new(pair[0], pair[1])).collect()
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.a.y == self.b.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.a.x == self.b.x
+    }
+
+    pub fn is_axis_aligned(&self) -> bool {
+        self.is_horizontal() || self.is_vertical()
+    }
+
+    pub fn delta(&self) -> Point {
+        self.b - self.a
+    }
+
+    pub fn length(&self) -> f64 {
+        (self.a.euclidean_sq(self.b) as f64).sqrt()
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::new(self.a, self.a).including(self.b)
+    }
+
+    /// Iterates over every point with integer coordinates that lies exactly on
+    /// the segment, from a to b
+    pub fn lattice_points(&self) -> impl Iterator<Item=Point> {
+        let delta = self.delta();
+        let steps = gcd(delta.x, delta.y);
+        let step = if steps == 0 { delta } else { delta / steps };
+        let a = self.a;
+
+        (0..=steps).map(move |i| a + step * i)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.delta().cross(point - self.a) == 0 && self.bounds().contains(point)
+    }
+
+    pub fn intersects(&self, other: &Segment) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Finds where two segments meet, or None if they don't
+    pub fn intersection(&self, other: &Segment) -> Option<Intersection> {
+        if self.a == self.b {
+            return other.contains(self.a).then_some(Intersection::Point(self.a));
+        }
+        if other.a == other.b {
+            return self.contains(other.a).then_some(Intersection::Point(other.a));
+        }
+
+        let d1 = self.delta();
+        let d2 = other.delta();
+        let offset = other.a - self.a;
+        let denominator = d1.cross(d2);
+
+        if denominator == 0 {
+            return if offset.cross(d1) == 0 { self.overlap(other) } else { None };
+        }
+
+        // self.a + d1 * t == other.a + d2 * u, with t and u kept as fractions over
+        // a positive denominator
+        let sign = denominator.signum();
+        let (t, u, denominator) = (offset.cross(d2) * sign, offset.cross(d1) * sign, denominator * sign);
+        if !(0..=denominator).contains(&t) || !(0..=denominator).contains(&u) {
+            return None;
+        }
+
+        let numerator = self.a * denominator + d1 * t;
+        if numerator.x % denominator == 0 && numerator.y % denominator == 0 {
+            Some(Intersection::Point(numerator / denominator))
+        } else {
+            Some(Intersection::Fractional(
+                numerator.x as f64 / denominator as f64,
+                numerator.y as f64 / denominator as f64,
+            ))
+        }
+    }
+
+    // Intersects two collinear segments
+    fn overlap(&self, other: &Segment) -> Option<Intersection> {
+        let d1 = self.delta();
+        let ordered = |a: Point, b: Point| if a.dot(d1) <= b.dot(d1) { (a, b) } else { (b, a) };
+
+        let (min1, max1) = ordered(self.a, self.b);
+        let (min2, max2) = ordered(other.a, other.b);
+        let start = if min1.dot(d1) >= min2.dot(d1) { min1 } else { min2 };
+        let end = if max1.dot(d1) <= max2.dot(d1) { max1 } else { max2 };
+
+        match start.dot(d1).cmp(&end.dot(d1)) {
+            std::cmp::Ordering::Greater => None,
+            std::cmp::Ordering::Equal => Some(Intersection::Point(start)),
+            std::cmp::Ordering::Less => Some(Intersection::Overlap(Segment::new(start, end))),
+        }
+    }
+}
+
+/// A simple polygon with integer vertices. The last vertex connects back to the
+/// first.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    pub vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Self {
+        Self { vertices }
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item=Segment> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().zip(next).map(|(a, b)| Segment::new(*a, *b))
+    }
+
+    /// Twice the enclosed area, which is always an integer. Computed with the
+    /// shoelace formula.
+    pub fn double_area(&self) -> isize {
+        self.edges().map(|edge| edge.a.cross(edge.b)).sum::<isize>().abs()
+    }
+
+    pub fn area(&self) -> f64 {
+        self.double_area() as f64 / 2.0
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|edge| edge.length()).sum()
+    }
+
+    /// The number of integer points on the edges of the polygon
+    pub fn boundary_points(&self) -> usize {
+        self.edges().map(|edge| gcd(edge.delta().x, edge.delta().y) as usize).sum()
+    }
+
+    /// The number of integer points strictly inside the polygon, from Pick's theorem.
+    /// Degenerate polygons with no area have no interior.
+    pub fn interior_points(&self) -> usize {
+        let double_area = self.double_area() as usize;
+        if double_area == 0 {
+            return 0;
+        }
+
+        (double_area + 2 - self.boundary_points()) / 2
+    }
+
+    /// Whether the point is inside the polygon or on one of its edges
+    pub fn contains(&self, point: Point) -> bool {
+        let mut winding = 0;
+
+        for edge in self.edges() {
+            if edge.contains(point) {
+                return true;
+            }
+
+            let side = edge.delta().cross(point - edge.a);
+            if edge.a.y <= point.y && edge.b.y > point.y && side > 0 {
+                winding += 1;
+            } else if edge.a.y > point.y && edge.b.y <= point.y && side < 0 {
+                winding -= 1;
+            }
+        }
+
+        winding != 0
+    }
+}
+
+#[cfg(test)]
+mod geometry_tests {
+    use super::*;
+
+    #[test]
+    fn test_segment() {
+        let a = Segment::new(Point::new(0, 0), Point::new(4, 4));
+        let b = Segment::new(Point::new(0, 4), Point::new(4, 0));
+        let c = Segment::new(Point::new(0, 1), Point::new(1, 0));
+
+        assert_eq!(a.intersection(&b), Some(Intersection::Point(Point::new(2, 2))));
+        assert_eq!(a.intersection(&c), Some(Intersection::Fractional(0.5, 0.5)));
+        assert!(!b.intersects(&c));
+
+        let overlapping = Segment::new(Point::new(6, 6), Point::new(2, 2));
+        assert_eq!(a.intersection(&overlapping), Some(Intersection::Overlap(Segment::new(Point::new(2, 2), Point::new(4, 4)))));
+        assert_eq!(a.intersection(&Segment::new(Point::new(4, 4), Point::new(5, 5))), Some(Intersection::Point(Point::new(4, 4))));
+        assert_eq!(a.intersection(&Segment::new(Point::new(5, 5), Point::new(6, 6))), None);
+        assert_eq!(a.intersection(&Segment::new(Point::new(1, 0), Point::new(5, 4))), None);
+
+        let horizontal = Segment::new(Point::new(-1, 3), Point::new(5, 3));
+        let vertical = Segment::new(Point::new(2, 0), Point::new(2, 2));
+        assert!(horizontal.is_horizontal() && vertical.is_vertical());
+        assert!(!horizontal.intersects(&vertical));
+        assert!(horizontal.contains(Point::new(0, 3)));
+
+        assert_eq!(Segment::new(Point::new(0, 0), Point::new(6, 3)).lattice_points().count(), 4);
+        assert_eq!(Segment::path(&[Point::new(0, 0), Point::new(0, 2), Point::new(3, 2)]).len(), 2);
+    }
+
+    #[test]
+    fn test_polygon() {
+        let square = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)]);
+        assert_eq!(square.area(), 16.0);
+        assert_eq!(square.perimeter(), 16.0);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert!(square.contains(Point::new(2, 2)) && square.contains(Point::new(4, 1)));
+        assert!(!square.contains(Point::new(5, 2)) && !square.contains(Point::new(-1, 0)));
+
+        let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 3)]);
+        assert_eq!(triangle.double_area(), 12);
+        assert_eq!(triangle.perimeter(), 12.0);
+        assert_eq!(triangle.interior_points(), 3);
+        assert!(triangle.contains(Point::new(1, 1)) && !triangle.contains(Point::new(3, 2)));
+
+        let line = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0)]);
+        assert_eq!(line.area(), 0.0);
+        assert_eq!(line.interior_points(), 0);
+        assert_eq!(Polygon::new(vec![Point::new(0, 0), Point::new(2, 2), Point::new(4, 4)]).interior_points(), 0);
+        assert_eq!(Polygon::new(vec![]).interior_points(), 0);
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use crate::utils::{Point, Direction};
+use std::rc::Rc;
+use crate::utils::{neighbor_offsets, Point, Direction, Topology, Bounded, Rect, Transform2};
 
 /// Represents a 2D grid of elements of type T
 /// 
@@ -9,6 +10,12 @@ use crate::utils::{Point, Direction};
 /// doesn't involve remembering how the items are laid out in memory. For example,
 /// moving from a node down along the y axis can be done with a method named "down".
 /// 
+/// Elements are addressed in two ways. Methods taking x and y as usize use indices
+/// into the grid, which always start at (0, 0). Methods taking a Point use world
+/// coordinates, which are the indices shifted by the grid's origin. This allows
+/// a grid to cover an area of a puzzle that doesn't start at (0, 0), or that has
+/// negative coordinates.
+/// 
 /// # Examples
 /// ```
 /// let grid = Grid::new(my_vector);
@@ -27,6 +34,9 @@ use crate::utils::{Point, Direction};
 /// ```
 pub struct Grid<T> {
     items: Vec<Vec<T>>,
+    topology: Rc<dyn Topology>,
+    // The world coordinates of the element at index (0, 0)
+    origin: Point,
 }
 
 /// A specific position on a Grid.
@@ -34,16 +44,86 @@ pub struct Grid<T> {
 /// Stores the x and y coordinate of a particular point on the Grid. Can access
 /// the underlying data via a Deref implementation. Has various methods for 
 /// traversal. 
-#[derive(Clone, Copy)]
+/// 
+/// Nodes created from a GridView are confined to that view: their coordinates are
+/// relative to the view's top-left corner, and navigation stops at the view's
+/// edges rather than the edges of the underlying Grid. What happens at an edge is
+/// decided by the Topology of the Grid.
 pub struct GridNode<'a, T> {
     x: usize,
     y: usize,
     grid: &'a Grid<T>,
+    window: Window,
+}
+
+/// A borrowed rectangular window into a Grid.
+/// 
+/// Exposes the same at/GridNode interface as Grid without copying any of the
+/// underlying data. Created with Grid::view.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    window: Window,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Window {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Window {
+    fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+}
+
+// The topology can't be compared or hashed, so grids are equal if they have the
+// same elements at the same origin
+impl<T: Clone> Clone for Grid<T> {
+    fn clone(&self) -> Self {
+        Grid { items: self.items.clone(), topology: self.topology.clone(), origin: self.origin }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items && self.origin == other.origin
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+impl<T: Hash> Hash for Grid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.items.hash(state);
+        self.origin.hash(state);
+    }
+}
+
+// Clone and Copy are implemented by hand since deriving them would require T to
+// be Copy, even though only a reference to the grid is stored
+impl<'a, T> Clone for GridNode<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for GridNode<'a, T> {}
+
+impl<'a, T> Clone for GridView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<'a, T> Copy for GridView<'a, T> {}
+
 impl<'a, T> PartialEq for GridNode<'a, T> {
     fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y && (self.grid as *const Grid<T>) == (other.grid as *const Grid<T>)
+        self.x == other.x && self.y == other.y && self.window == other.window &&
+            (self.grid as *const Grid<T>) == (other.grid as *const Grid<T>)
     }
 }
 
@@ -53,34 +133,55 @@ impl<'a, T> Hash for GridNode<'a, T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.x.hash(state);
         self.y.hash(state);
+        self.window.hash(state);
         (self.grid as *const Grid<T>).hash(state);
     }
 }
 
 impl<T> Grid<T> {
     pub fn new(items: Vec<Vec<T>>) -> Self {
-        Grid { items }
+        Grid { items, topology: Rc::new(Bounded), origin: Point::new(0, 0) }
+    }
+
+    /// Sets the world coordinates of the element at index (0, 0)
+    pub fn with_origin(mut self, origin: Point) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    /// The world coordinates covered by the grid
+    pub fn bounds(&self) -> Rect {
+        Rect::new(self.origin, self.origin + Point::new(self.x_len() as isize - 1, self.y_len() as isize - 1))
+    }
+
+    /// Sets the topology used when navigating off the edges of the grid
+    pub fn with_topology<P: Topology + 'static>(mut self, topology: P) -> Self {
+        self.topology = Rc::new(topology);
+        self
     }
 
     pub fn at(&self, x: usize, y: usize) -> Option<GridNode<T>> {
-        if y >= self.y_len() || x >= self.x_len() {
-            None
-        } else {
-            Some(GridNode { x, y, grid: &self })
-        }
+        self.as_view().at(x, y)
     }
 
     pub fn at_point(&self, point: Point) -> Option<GridNode<T>> {
-        self.at(point.x as usize, point.y as usize)
+        self.as_view().at_point(point)
     }
 
     pub fn set_at(&mut self, point: GridNode<T>, value: T) {
         assert_eq!(self as *const _, point.grid as *const _);
-        self.items[point.y as usize][point.x as usize] = value;
+        self.set_node(point, value);
     }
 
     pub fn set_at_point(&mut self, point: Point, value: T) {
-        self.items[point.y as usize][point.x as usize] = value;
+        let (x, y) = (point - self.origin).try_into_usize_pair()
+            .unwrap_or_else(|| panic!("Point {:?} is outside of the grid", point));
+
+        self.items[y][x] = value;
     }
 
     pub fn x_len(&self) -> usize {
@@ -102,7 +203,28 @@ impl<T> Grid<T> {
     }
 
     pub fn set_node(&mut self, node: GridNode<T>, value: T) {
-        self.items[node.y][node.x] = value;
+        self.items[node.window.y + node.y][node.window.x + node.x] = value;
+    }
+
+    /// Returns a view of the whole grid
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            window: Window { x: 0, y: 0, width: self.x_len(), height: self.y_len() },
+        }
+    }
+
+    /// Returns a view of the width x height rectangle whose top-left corner is at
+    /// (x, y). The rectangle must fit inside the grid.
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> GridView<'_, T> {
+        self.as_view().view(x, y, width, height)
+    }
+
+    /// Replaces every element of the grid at once, keeping its topology and origin,
+    /// and returns the old elements. The new elements must have the same dimensions.
+    pub(super) fn replace_items(&mut self, items: Vec<Vec<T>>) -> Vec<Vec<T>> {
+        assert!(items.len() == self.y_len() && items.iter().all(|row| row.len() == self.x_len()));
+        std::mem::replace(&mut self.items, items)
     }
 
     fn get_value(&self, x: usize, y: usize) -> &T {
@@ -117,6 +239,219 @@ impl<T> Grid<T> {
     pub fn iter(&self) -> std::slice::Iter<Vec<T>> {
         self.items.iter()
     }
+
+    /// Iterates over the rows mutably. Rows are slices so their lengths can't change.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item=&mut [T]> + '_ {
+        self.items.iter_mut().map(Vec::as_mut_slice)
+    }
+
+    /// Iterates over every node in the grid in row-major order
+    pub fn nodes(&self) -> impl Iterator<Item=GridNode<'_, T>> {
+        self.as_view().nodes()
+    }
+
+    /// Iterates over every (x, y) coordinate pair in the grid in row-major order
+    pub fn positions(&self) -> impl Iterator<Item=(usize, usize)> {
+        self.as_view().positions()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=GridNodeIterator<'_, T>> {
+        self.as_view().rows()
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item=GridNodeIterator<'_, T>> {
+        self.as_view().columns()
+    }
+
+    pub fn row(&self, y: usize) -> GridNodeIterator<'_, T> {
+        self.as_view().row(y)
+    }
+
+    pub fn column(&self, x: usize) -> GridNodeIterator<'_, T> {
+        self.as_view().column(x)
+    }
+
+    /// Creates a new grid of the same dimensions by applying a function to every
+    /// element
+    pub fn map<U, F: FnMut(&T) -> U>(&self, mut f: F) -> Grid<U> {
+        Grid {
+            items: self.items.iter().map(|row| row.iter().map(&mut f).collect()).collect(),
+            topology: self.topology.clone(),
+            origin: self.origin,
+        }
+    }
+
+    /// Adds the given number of rows or columns to each edge, filled with a value.
+    /// The origin moves with the top-left corner, so every existing element keeps
+    /// its world coordinates.
+    pub fn grow(&mut self, left: usize, up: usize, right: usize, down: usize, fill: T) where T: Clone {
+        let width = left + self.x_len() + right;
+
+        for row in self.items.iter_mut() {
+            row.splice(0..0, vec![fill.clone(); left]);
+            row.resize(width, fill.clone());
+        }
+
+        self.items.splice(0..0, vec![vec![fill.clone(); width]; up]);
+        self.items.resize(self.items.len() + down, vec![fill; width]);
+        self.origin -= Point::new(left as isize, up as isize);
+    }
+
+    /// Grows the grid just enough for it to contain the given world coordinates
+    pub fn grow_to_include(&mut self, point: Point, fill: T) where T: Clone {
+        let index = point - self.origin;
+        let left = (-index.x).max(0) as usize;
+        let up = (-index.y).max(0) as usize;
+        let right = (index.x - self.x_len() as isize + 1).max(0) as usize;
+        let down = (index.y - self.y_len() as isize + 1).max(0) as usize;
+
+        self.grow(left, up, right, down, fill);
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Rebuilds the grid with rearranged items, keeping its topology. The origin
+    // isn't kept since the items no longer sit at the same coordinates.
+    fn with_items(&self, items: Vec<Vec<T>>) -> Grid<T> {
+        Grid { items, topology: self.topology.clone(), origin: Point::new(0, 0) }
+    }
+
+    /// Swaps the x and y axes, so that rows become columns
+    pub fn transpose(&self) -> Grid<T> {
+        self.with_items(
+            (0..self.x_len())
+                .map(|x| self.items.iter().map(|row| row[x].clone()).collect())
+                .collect()
+        )
+    }
+
+    /// Rotates the grid by 90 degrees clockwise. The bottom-left element ends up
+    /// in the top-left corner.
+    pub fn rotate_cw(&self) -> Grid<T> {
+        self.with_items(
+            (0..self.x_len())
+                .map(|x| self.items.iter().rev().map(|row| row[x].clone()).collect())
+                .collect()
+        )
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise. The top-right element ends
+    /// up in the top-left corner.
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        self.with_items(
+            (0..self.x_len())
+                .rev()
+                .map(|x| self.items.iter().map(|row| row[x].clone()).collect())
+                .collect()
+        )
+    }
+
+    /// Mirrors the grid left-to-right
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        self.with_items(self.items.iter().map(|row| row.iter().rev().cloned().collect()).collect())
+    }
+
+    /// Mirrors the grid top-to-bottom
+    pub fn flip_vertical(&self) -> Grid<T> {
+        self.with_items(self.items.iter().rev().cloned().collect())
+    }
+
+    /// Applies one of the eight orientations from Transform2::all() to the grid.
+    /// The result is shifted so that its top-left corner is at index (0, 0).
+    pub fn transformed(&self, transform: Transform2) -> Grid<T> {
+        assert!(transform.is_orientation(), "Grids can only be rotated and reflected");
+
+        if self.x_len() == 0 || self.y_len() == 0 {
+            return self.with_items(vec![]);
+        }
+
+        let far_corner = transform.apply(Point::new(self.x_len() as isize - 1, self.y_len() as isize - 1));
+        let min = Point::new(far_corner.x.min(0), far_corner.y.min(0));
+        let size = far_corner.abs() + Point::new(1, 1);
+        let inverse = transform.inverse().unwrap();
+
+        self.with_items(
+            (0..size.y)
+                .map(|y| (0..size.x).map(|x| {
+                    let source = inverse.apply(Point::new(x, y) + min);
+                    self.items[source.y as usize][source.x as usize].clone()
+                }).collect())
+                .collect()
+        )
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn at(&self, x: usize, y: usize) -> Option<GridNode<'a, T>> {
+        if y >= self.y_len() || x >= self.x_len() {
+            None
+        } else {
+            Some(GridNode { x, y, grid: self.grid, window: self.window })
+        }
+    }
+
+    /// Returns the node at the given world coordinates
+    pub fn at_point(&self, point: Point) -> Option<GridNode<'a, T>> {
+        let index = point - self.grid.origin - Point::new(self.window.x as isize, self.window.y as isize);
+        let (x, y) = index.try_into_usize_pair()?;
+        self.at(x, y)
+    }
+
+    pub fn x_len(&self) -> usize {
+        self.window.width
+    }
+
+    pub fn y_len(&self) -> usize {
+        self.window.height
+    }
+
+    /// Returns a view of a rectangle inside of this view. The coordinates are
+    /// relative to this view's top-left corner.
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> GridView<'a, T> {
+        assert!(x + width <= self.x_len() && y + height <= self.y_len(), "View does not fit inside the grid");
+
+        GridView {
+            grid: self.grid,
+            window: Window { x: self.window.x + x, y: self.window.y + y, width, height },
+        }
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item=GridNode<'a, T>> {
+        self.rows().flatten()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item=(usize, usize)> {
+        let width = self.x_len();
+        (0..self.y_len()).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=GridNodeIterator<'a, T>> {
+        let view = *self;
+        (0..self.y_len()).map(move |y| view.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item=GridNodeIterator<'a, T>> {
+        let view = *self;
+        (0..self.x_len()).map(move |x| view.column(x))
+    }
+
+    /// Iterates over the nodes in row y from left to right. Empty if y is out of
+    /// bounds. The topology is ignored, so each node is visited exactly once.
+    pub fn row(&self, y: usize) -> GridNodeIterator<'a, T> {
+        GridNodeIterator::within_window(self.at(0, y), 1, 0)
+    }
+
+    /// Iterates over the nodes in column x from top to bottom. Empty if x is out of
+    /// bounds. The topology is ignored, so each node is visited exactly once.
+    pub fn column(&self, x: usize) -> GridNodeIterator<'a, T> {
+        GridNodeIterator::within_window(self.at(x, 0), 0, 1)
+    }
+
+    /// Copies the contents of this view into a new Grid
+    pub fn to_grid(self) -> Grid<T> where T: Clone {
+        let rows = self.grid.items[self.window.y..self.window.y + self.window.height].iter();
+        Grid::new(rows.map(|row| row[self.window.x..self.window.x + self.window.width].to_vec()).collect())
+    }
 }
 
 impl<'a, T> GridNode<'a, T> {
@@ -128,36 +463,73 @@ impl<'a, T> GridNode<'a, T> {
         self.y
     }
 
+    /// The world coordinates of this node
+    pub fn point(&self) -> Point {
+        self.grid.origin + Point::new((self.window.x + self.x) as isize, (self.window.y + self.y) as isize)
+    }
+
+    /// Moves by an arbitrary delta. If the move leaves the grid, the topology is
+    /// consulted once for the final position.
     pub fn moved(&self, dx: isize, dy: isize) -> Option<GridNode<'a, T>> {
-        let new_x = self.x as isize + dx;
-        let new_y = self.y as isize + dy;
+        self.resolve(self.x as isize + dx, self.y as isize + dy, direction_of(dx, dy))
+            .map(|(node, _)| node)
+    }
 
-        if new_x < 0 || new_x >= self.grid.x_len() as isize || new_y < 0 || new_y >= self.grid.y_len() as isize {
-            None
-        } else {
-            Some(GridNode {
-                x: new_x as usize,
-                y: new_y as usize,
-                grid: self.grid
-            })
+    /// Moves n steps in a direction, one step at a time, following any changes of
+    /// direction made by the topology when crossing an edge
+    pub fn move_in_direction(&self, direction: Direction, n: isize) -> Option<GridNode<'a, T>> {
+        let (mut direction, steps) = if n < 0 { (direction.flipped(), -n) } else { (direction, n) };
+
+        // Moving in a straight line can't leave the grid and come back, so if the
+        // destination is in bounds then no edge was crossed on the way
+        let new_x = self.x as isize + steps * direction.dx();
+        let new_y = self.y as isize + steps * direction.dy();
+        if self.window.contains(new_x, new_y) {
+            return Some(self.with_position(new_x as usize, new_y as usize));
+        }
+
+        let mut node = *self;
+        for _ in 0..steps {
+            (node, direction) = node.step(direction)?;
         }
+
+        Some(node)
     }
 
-    pub fn move_in_direction(&self, direction: Direction, n: isize) -> Option<GridNode<'a, T>> {
-        let new_x = self.x as isize + n * direction.dx();
-        let new_y = self.y as isize + n * direction.dy();
+    /// Moves a single step in a direction. Returns the new node along with the
+    /// direction of travel after the step, which is only different from the given
+    /// direction if the topology of the grid changed it.
+    pub fn step(&self, direction: Direction) -> Option<(GridNode<'a, T>, Direction)> {
+        let new_x = self.x as isize + direction.dx();
+        let new_y = self.y as isize + direction.dy();
 
-        if new_x < 0 || new_x >= self.grid.x_len() as isize || new_y < 0 || new_y >= self.grid.y_len() as isize {
-            None
+        self.resolve(new_x, new_y, Some(direction))
+            .map(|(node, new_direction)| (node, new_direction.unwrap_or(direction)))
+    }
+
+    fn resolve(&self, x: isize, y: isize, direction: Option<Direction>) -> Option<(GridNode<'a, T>, Option<Direction>)> {
+        if self.window.contains(x, y) {
+            return Some((self.with_position(x as usize, y as usize), direction));
+        }
+
+        let (point, direction) = self.grid.topology.cross_edge(
+            Point::new(x, y),
+            direction,
+            self.window.width,
+            self.window.height,
+        )?;
+
+        if self.window.contains(point.x, point.y) {
+            Some((self.with_position(point.x as usize, point.y as usize), direction))
         } else {
-            Some(GridNode {
-                x: new_x as usize,
-                y: new_y as usize,
-                grid: self.grid
-            })
+            None
         }
     }
 
+    fn with_position(&self, x: usize, y: usize) -> GridNode<'a, T> {
+        GridNode { x, y, grid: self.grid, window: self.window }
+    }
+
     pub fn left(&self) -> Option<GridNode<'a, T>> {
         self.moved(-1, 0)
     }
@@ -174,21 +546,50 @@ impl<'a, T> GridNode<'a, T> {
         self.moved(0, 1)
     }
 
-    // TODO: Expose interface for custom deltas/inclusion if necessary
+    /// Casts a ray from this node, visiting every node on the line in the given
+    /// direction or (dx, dy) delta, not including this node. The delta is reduced
+    /// to its smallest integer step, so a ray of (4, 2) visits the same nodes as a
+    /// ray of (2, 1).
+    /// 
+    /// # Examples
+    /// ```
+    /// let visible_trees = node.ray(Direction::Left).count_visible(|height| *height >= *node);
+    /// let first_asteroid = node.ray((3, -2)).first_match(|c| *c == '#');
+    /// ```
+    pub fn ray<D: Into<(isize, isize)>>(&self, delta: D) -> GridNodeIterator<'a, T> {
+        let (dx, dy) = delta.into();
+        assert!(dx != 0 || dy != 0, "Ray delta must be non-zero");
+
+        let divisor = num::integer::gcd(dx, dy);
+        GridNodeIterator::new(*self, dx / divisor, dy / divisor)
+    }
+
     pub fn left_iter(&self) -> GridNodeIterator<'a, T> {
-        GridNodeIterator::new(self.grid, self.x, self.y, -1, 0)
+        GridNodeIterator::new(*self, -1, 0)
     }
 
     pub fn right_iter(&self) -> GridNodeIterator<'a, T> {
-        GridNodeIterator::new(self.grid, self.x, self.y, 1, 0)
+        GridNodeIterator::new(*self, 1, 0)
     }
 
     pub fn up_iter(&self) -> GridNodeIterator<'a, T> {
-        GridNodeIterator::new(self.grid, self.x, self.y, 0, -1)
+        GridNodeIterator::new(*self, 0, -1)
     }
 
     pub fn down_iter(&self) -> GridNodeIterator<'a, T> {
-        GridNodeIterator::new(self.grid, self.x, self.y, 0, 1)
+        GridNodeIterator::new(*self, 0, 1)
+    }
+
+    /// Iterates over the orthogonal neighbors of this node that exist
+    pub fn neighbors(&self) -> impl Iterator<Item=GridNode<'a, T>> {
+        let node = *self;
+        Direction::all().filter_map(move |dir| node.step(dir).map(|(n, _)| n))
+    }
+
+    /// Iterates over the neighbors of this node that exist, including diagonals
+    pub fn neighbors_diagonal(&self) -> impl Iterator<Item=GridNode<'a, T>> {
+        let node = *self;
+        neighbor_offsets::<2>(true).filter_map(move |[dx, dy]| node.moved(dx, dy))
     }
 }
 
@@ -196,21 +597,59 @@ impl<'a, T> std::ops::Deref for GridNode<'a, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        self.grid.get_value(self.x, self.y)
+        self.grid.get_value(self.window.x + self.x, self.window.y + self.y)
     }
 }
 
+/// Iterates over nodes by repeatedly moving by a delta.
+/// 
+/// On grids whose topology wraps around, the iterator stops when it would return
+/// to the node it started from. Rows and columns never cross an edge.
 pub struct GridNodeIterator<'a, T> {
-    grid: &'a Grid<T>,
-    x: usize,
-    y: usize,
+    next: Option<GridNode<'a, T>>,
+    origin: Option<GridNode<'a, T>>,
     delta_x: isize,
     delta_y: isize,
+    // Whether to stop at the edge of the window instead of consulting the topology
+    bounded: bool,
 }
 
 impl<'a, T> GridNodeIterator<'a, T> {
-    fn new(grid: &'a Grid<T>, x: usize, y: usize, delta_x: isize, delta_y: isize) -> Self {
-        Self { grid, x, y, delta_x, delta_y }
+    /// Creates an iterator over the nodes after (but not including) the given node
+    fn new(node: GridNode<'a, T>, delta_x: isize, delta_y: isize) -> Self {
+        let mut iter = Self::starting_at(Some(node), delta_x, delta_y);
+        iter.next();
+        iter
+    }
+
+    /// Creates an iterator whose first element is the given node
+    fn starting_at(first: Option<GridNode<'a, T>>, delta_x: isize, delta_y: isize) -> Self {
+        Self { next: first, origin: first, delta_x, delta_y, bounded: false }
+    }
+
+    /// Creates an iterator whose first element is the given node, and which stops
+    /// at the edge of the window regardless of the topology
+    fn within_window(first: Option<GridNode<'a, T>>, delta_x: isize, delta_y: isize) -> Self {
+        Self { bounded: true, ..Self::starting_at(first, delta_x, delta_y) }
+    }
+
+    /// Yields nodes up to and including the first one that is a blocker
+    pub fn take_until_blocked<P: Fn(&T) -> bool>(self, is_blocker: P) -> impl Iterator<Item=GridNode<'a, T>> {
+        let mut blocked = false;
+        self.take_while(move |node| {
+            let visible = !blocked;
+            blocked = blocked || is_blocker(node);
+            visible
+        })
+    }
+
+    pub fn first_match<P: Fn(&T) -> bool>(mut self, predicate: P) -> Option<GridNode<'a, T>> {
+        self.find(|node| predicate(node))
+    }
+
+    /// Counts the nodes that can be seen before (and including) the first blocker
+    pub fn count_visible<P: Fn(&T) -> bool>(self, is_blocker: P) -> usize {
+        self.take_until_blocked(is_blocker).count()
     }
 }
 
@@ -218,16 +657,39 @@ impl<'a, T> Iterator for GridNodeIterator<'a, T> {
     type Item = GridNode<'a, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next_x = self.x as isize + self.delta_x;
-        let next_y = self.y as isize + self.delta_y;
-        if next_x < 0 || next_y < 0 {
-            return None;
+        let node = self.next?;
+        let new_x = node.x as isize + self.delta_x;
+        let new_y = node.y as isize + self.delta_y;
+
+        if self.bounded {
+            self.next = node.window.contains(new_x, new_y)
+                .then(|| node.with_position(new_x as usize, new_y as usize));
+            return Some(node);
         }
 
-        let next_node = self.grid.at(next_x as usize, next_y as usize);
-        self.x = next_x as usize;
-        self.y = next_y as usize;
-        next_node        
+        self.next = match node.resolve(new_x, new_y, direction_of(self.delta_x, self.delta_y)) {
+            Some((next, direction)) if Some(next) != self.origin => {
+                if let Some(direction) = direction {
+                    self.delta_x = direction.dx();
+                    self.delta_y = direction.dy();
+                }
+                Some(next)
+            }
+            _ => None,
+        };
+
+        Some(node)
+    }
+}
+
+/// The direction of a delta, if it lies along a single axis
+fn direction_of(dx: isize, dy: isize) -> Option<Direction> {
+    match (dx.signum(), dy.signum()) {
+        (-1, 0) => Some(Direction::Left),
+        (1, 0) => Some(Direction::Right),
+        (0, -1) => Some(Direction::Up),
+        (0, 1) => Some(Direction::Down),
+        _ => None,
     }
 }
 
@@ -246,3 +708,124 @@ impl<T: fmt::Debug> fmt::Display for Grid<T> {
         writeln!(f, "]")
     }
 }
+
+#[cfg(test)]
+mod grid_tests {
+    use super::*;
+    use crate::utils::{Torus, Custom};
+
+    fn values(grid: &Grid<u8>) -> Vec<Vec<u8>> {
+        grid.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_transforms() {
+        // 1 2 3
+        // 4 5 6
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(values(&grid.transpose()), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(values(&grid.rotate_cw()), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+        assert_eq!(values(&grid.rotate_ccw()), vec![vec![3, 6], vec![2, 5], vec![1, 4]]);
+        assert_eq!(values(&grid.flip_horizontal()), vec![vec![3, 2, 1], vec![6, 5, 4]]);
+        assert_eq!(values(&grid.flip_vertical()), vec![vec![4, 5, 6], vec![1, 2, 3]]);
+        assert_eq!(values(&grid.rotate_cw().rotate_ccw()), values(&grid));
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        let view = grid.view(1, 1, 2, 2);
+
+        assert_eq!(*view.at(0, 0).unwrap(), 5);
+        assert!(view.at(2, 0).is_none());
+        assert_eq!(view.at(0, 0).unwrap().right_iter().map(|n| *n).collect::<Vec<_>>(), vec![6]);
+        assert!(view.at(0, 0).unwrap().left().is_none());
+        assert_eq!(*view.view(1, 0, 1, 2).at(0, 1).unwrap(), 9);
+        assert_eq!(values(&view.to_grid()), vec![vec![5, 6], vec![8, 9]]);
+    }
+
+    #[test]
+    fn test_iteration() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(grid.nodes().map(|n| *n).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+        assert_eq!(grid.column(2).map(|n| *n).collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.row(2).count(), 0);
+        assert_eq!(grid.columns().map(|c| c.map(|n| *n).sum::<u8>()).collect::<Vec<_>>(), vec![5, 7, 9]);
+        assert_eq!(grid.view(1, 0, 2, 2).nodes().map(|n| (n.x(), n.y(), *n)).last(), Some((1, 1, 6)));
+        assert_eq!(values(&grid.map(|v| v * 2)), vec![vec![2, 4, 6], vec![8, 10, 12]]);
+    }
+
+    #[test]
+    fn test_topology() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).with_topology(Torus);
+        let node = grid.at(0, 0).unwrap();
+
+        assert_eq!(*node.left().unwrap(), 3);
+        assert_eq!(*node.up().unwrap(), 4);
+        assert_eq!(*node.move_in_direction(Direction::Right, 4).unwrap(), 2);
+        assert_eq!(*node.moved(-4, 3).unwrap(), 6);
+        assert_eq!(node.left_iter().map(|n| *n).collect::<Vec<_>>(), vec![3, 2]);
+        assert_eq!(grid.row(1).count(), 3);
+
+        // Walking off the right edge sends you back in along the bottom row, heading left
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).with_topology(Custom::new(|p: Point, d, w, _| {
+            (d == Some(Direction::Right)).then(|| (Point::new(w as isize - 1, 1 - p.y), Some(Direction::Left)))
+        }));
+        let node = grid.at(2, 0).unwrap();
+
+        assert_eq!(node.step(Direction::Right).map(|(n, d)| (*n, d)), Some((6, Direction::Left)));
+        assert_eq!(*node.move_in_direction(Direction::Right, 2).unwrap(), 5);
+        assert_eq!(grid.at(0, 0).unwrap().right_iter().map(|n| *n).collect::<Vec<_>>(), vec![2, 3, 6, 5, 4]);
+        assert!(node.up().is_none());
+
+        // Whole-grid iteration ignores the topology
+        assert_eq!(grid.row(0).map(|n| *n).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(grid.column(2).map(|n| *n).collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.nodes().map(|n| *n).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).with_topology(Torus).nodes().count(), 6);
+
+        // Rearranging the grid keeps its topology
+        let transposed = grid.transpose().transpose();
+        let node = transposed.at(2, 0).unwrap();
+        assert_eq!(node.step(Direction::Right).map(|(n, d)| (*n, d)), Some((6, Direction::Left)));
+    }
+
+    #[test]
+    fn test_origin() {
+        let mut grid = Grid::new(vec![vec![1, 2], vec![3, 4]]).with_origin(Point::new(-1, 5));
+
+        assert_eq!(*grid.at_point(Point::new(0, 6)).unwrap(), 4);
+        assert!(grid.at_point(Point::new(1, 6)).is_none());
+        assert_eq!(grid.at(1, 0).unwrap().point(), Point::new(0, 5));
+        assert_eq!(grid.view(1, 1, 1, 1).at_point(Point::new(0, 6)).unwrap().point(), Point::new(0, 6));
+
+        grid.grow_to_include(Point::new(-3, 7), 0);
+        assert_eq!(grid.origin(), Point::new(-3, 5));
+        assert_eq!((grid.x_len(), grid.y_len()), (4, 3));
+        assert_eq!(*grid.at_point(Point::new(0, 6)).unwrap(), 4);
+
+        grid.set_at_point(Point::new(-3, 7), 9);
+        grid.grow(0, 1, 1, 0, 0);
+        assert_eq!(values(&grid), vec![vec![0; 5], vec![0, 0, 1, 2, 0], vec![0, 0, 3, 4, 0], vec![9, 0, 0, 0, 0]]);
+        assert_eq!(*grid.at_point(Point::new(-3, 7)).unwrap(), 9);
+        assert!(grid.at_point(Point::new(-4, 7)).is_none());
+        assert!(grid.view(1, 1, 2, 2).at_point(Point::new(-3, 6)).is_none());
+        assert_eq!(grid.bounds(), Rect::new(Point::new(-3, 4), Point::new(1, 7)));
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::new(vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10, 11, 12]]);
+        let node = grid.at(0, 0).unwrap();
+
+        assert_eq!(node.ray(Direction::Right).count_visible(|v| *v >= 3), 2);
+        assert_eq!(node.ray(Direction::Right).count_visible(|v| *v >= 100), 3);
+        assert_eq!(node.ray((1, 1)).map(|n| *n).collect::<Vec<_>>(), vec![6, 11]);
+        assert_eq!(node.ray((4, 2)).map(|n| *n).collect::<Vec<_>>(), vec![7]);
+        assert_eq!(node.ray(Direction::Down).first_match(|v| *v > 5).map(|n| *n), Some(9));
+        assert!(node.ray(Direction::Up).first_match(|_| true).is_none());
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+use crate::utils::{flat_index, Point3};
+
+/// Represents a 3D grid of elements of type T
+/// 
+/// The 3D counterpart to Grid. Elements are addressed by Point3, and any point
+/// with a negative or too large coordinate is outside of the grid.
+/// 
+/// # Examples
+/// ```
+/// let mut grid = Grid3::filled(20, 20, 20, false);
+/// grid.set(Point3::new(1, 2, 3), true);
+/// 
+/// // Everything reachable from the corner without passing through a filled cell
+/// let outside = grid.flood_fill(Point3::new(0, 0, 0), |filled| !filled);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    items: Vec<T>,
+    size: [usize; 3],
+}
+
+impl<T> Grid3<T> {
+    /// Creates a grid from nested vectors, indexed as items[z][y][x]
+    pub fn new(items: Vec<Vec<Vec<T>>>) -> Self {
+        let z_len = items.len();
+        let y_len = items.first().map_or(0, |plane| plane.len());
+        let x_len = items.first().and_then(|plane| plane.first()).map_or(0, |row| row.len());
+
+        let items = items.into_iter().flatten().flatten().collect::<Vec<_>>();
+        assert_eq!(items.len(), x_len * y_len * z_len, "Grid3 rows and planes must all be the same size");
+
+        Grid3 { items, size: [x_len, y_len, z_len] }
+    }
+
+    pub fn filled(x_len: usize, y_len: usize, z_len: usize, value: T) -> Self where T: Clone {
+        Grid3 { items: vec![value; x_len * y_len * z_len], size: [x_len, y_len, z_len] }
+    }
+
+    pub fn x_len(&self) -> usize {
+        self.size[0]
+    }
+
+    pub fn y_len(&self) -> usize {
+        self.size[1]
+    }
+
+    pub fn z_len(&self) -> usize {
+        self.size[2]
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        flat_index(self.size, point.into()).is_some()
+    }
+
+    pub fn get(&self, point: Point3) -> Option<&T> {
+        flat_index(self.size, point.into()).map(|i| &self.items[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point3) -> Option<&mut T> {
+        flat_index(self.size, point.into()).map(|i| &mut self.items[i])
+    }
+
+    pub fn set(&mut self, point: Point3, value: T) {
+        let index = flat_index(self.size, point.into()).expect("Point is outside of the grid");
+        self.items[index] = value;
+    }
+
+    /// Iterates over every point in the grid, with x changing fastest
+    pub fn positions(&self) -> impl Iterator<Item=Point3> {
+        let [x_len, y_len, z_len] = self.size;
+        (0..z_len).flat_map(move |z| {
+            (0..y_len).flat_map(move |y| (0..x_len).map(move |x| Point3::new(x as isize, y as isize, z as isize)))
+        })
+    }
+
+    /// Iterates over the face-sharing neighbors of a point that are in the grid
+    pub fn neighbors(&self, point: Point3) -> impl Iterator<Item=Point3> + '_ {
+        point.neighbors6().filter(|p| self.contains(*p))
+    }
+
+    /// Iterates over the face, edge and corner-sharing neighbors of a point that
+    /// are in the grid
+    pub fn neighbors_diagonal(&self, point: Point3) -> impl Iterator<Item=Point3> + '_ {
+        point.neighbors26().filter(|p| self.contains(*p))
+    }
+
+    /// Finds every point that can be reached from start by moving between
+    /// face-sharing neighbors that satisfy the predicate. The result is empty if
+    /// the start point itself doesn't satisfy it.
+    pub fn flood_fill<P: Fn(&T) -> bool>(&self, start: Point3, predicate: P) -> HashSet<Point3> {
+        let mut visited = HashSet::new();
+        match self.get(start) {
+            Some(value) if predicate(value) => {}
+            _ => return visited,
+        }
+
+        let mut to_process = vec![start];
+        visited.insert(start);
+
+        while let Some(point) = to_process.pop() {
+            for neighbor in self.neighbors(point) {
+                if predicate(&self.items[flat_index(self.size, neighbor.into()).unwrap()]) && visited.insert(neighbor) {
+                    to_process.push(neighbor);
+                }
+            }
+        }
+
+        visited
+    }
+}
+
+#[cfg(test)]
+mod grid3_tests {
+    use super::*;
+
+    #[test]
+    fn test_grid3() {
+        // A hollow 3x3x3 cube with its center cell empty
+        let mut grid = Grid3::filled(5, 5, 5, false);
+        for point in grid.positions().collect::<Vec<_>>() {
+            let inside = [point.x, point.y, point.z].iter().all(|c| (1..=3).contains(c));
+            grid.set(point, inside && point != Point3::new(2, 2, 2));
+        }
+
+        assert_eq!(grid.flood_fill(Point3::new(0, 0, 0), |filled| !filled).len(), 125 - 27);
+        assert_eq!(grid.flood_fill(Point3::new(2, 2, 2), |filled| !filled).len(), 1);
+        assert_eq!(grid.neighbors(Point3::new(0, 0, 0)).count(), 3);
+        assert_eq!(grid.neighbors_diagonal(Point3::new(2, 2, 2)).filter(|p| grid.get(*p) == Some(&true)).count(), 26);
+        assert!(grid.get(Point3::new(0, -1, 0)).is_none());
+
+        let grid = Grid3::new(vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5, 6], vec![7, 8]]]);
+        assert_eq!(grid.get(Point3::new(1, 0, 1)), Some(&6));
+        assert_eq!(Point3::new(-4, 0, 9).unit(), Point3::new(-1, 0, 1));
+    }
+}
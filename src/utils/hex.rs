@@ -0,0 +1,166 @@
+extern crate derive_more;
+
+use derive_more::{Add, Sub, Mul, AddAssign, SubAssign, MulAssign};
+use crate::utils::{Direction, Direction8};
+
+/// A direction on a grid of pointy-topped hexagons. Each hex has neighbors to the
+/// east and west, and four diagonal neighbors; there is no north or south.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub enum HexDirection {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+// Clockwise order, so rotating by 60 degrees is a step through this array
+const HEX_DIRECTIONS: [HexDirection; 6] = [
+    HexDirection::E,
+    HexDirection::SE,
+    HexDirection::SW,
+    HexDirection::W,
+    HexDirection::NW,
+    HexDirection::NE,
+];
+
+impl HexDirection {
+    /// Iterates over the six directions clockwise, starting with E
+    pub fn all() -> impl Iterator<Item=HexDirection> {
+        HEX_DIRECTIONS.into_iter()
+    }
+
+    pub fn flipped(&self) -> HexDirection {
+        self.rotated(3)
+    }
+
+    /// Rotates by 60 degrees clockwise
+    pub fn rotated_clockwise(&self) -> HexDirection {
+        self.rotated(1)
+    }
+
+    /// Rotates by 60 degrees counter-clockwise
+    pub fn rotated_counter_clockwise(&self) -> HexDirection {
+        self.rotated(5)
+    }
+
+    /// The change in axial coordinates when moving one hex in this direction
+    pub fn offset(&self) -> Hex {
+        match self {
+            HexDirection::E => Hex::new(1, 0),
+            HexDirection::SE => Hex::new(0, 1),
+            HexDirection::SW => Hex::new(-1, 1),
+            HexDirection::W => Hex::new(-1, 0),
+            HexDirection::NW => Hex::new(0, -1),
+            HexDirection::NE => Hex::new(1, -1),
+        }
+    }
+
+    fn rotated(&self, steps: usize) -> HexDirection {
+        HEX_DIRECTIONS[(*self as usize + steps) % 6]
+    }
+}
+
+impl From<HexDirection> for Direction8 {
+    fn from(direction: HexDirection) -> Self {
+        match direction {
+            HexDirection::E => Direction8::E,
+            HexDirection::SE => Direction8::SE,
+            HexDirection::SW => Direction8::SW,
+            HexDirection::W => Direction8::W,
+            HexDirection::NW => Direction8::NW,
+            HexDirection::NE => Direction8::NE,
+        }
+    }
+}
+
+impl TryFrom<Direction8> for HexDirection {
+    type Error = ();
+
+    fn try_from(direction: Direction8) -> Result<Self, Self::Error> {
+        match direction {
+            Direction8::E => Ok(HexDirection::E),
+            Direction8::SE => Ok(HexDirection::SE),
+            Direction8::SW => Ok(HexDirection::SW),
+            Direction8::W => Ok(HexDirection::W),
+            Direction8::NW => Ok(HexDirection::NW),
+            Direction8::NE => Ok(HexDirection::NE),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TryFrom<Direction> for HexDirection {
+    type Error = ();
+
+    fn try_from(direction: Direction) -> Result<Self, Self::Error> {
+        HexDirection::try_from(Direction8::from(direction))
+    }
+}
+
+/// A hex in axial coordinates. The third cube coordinate is implied by
+/// q + r + s = 0, and is available through s() and cube().
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Add, Sub, Mul, AddAssign, SubAssign, MulAssign)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl Hex {
+    pub fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    pub fn from_cube(q: isize, r: isize, s: isize) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates must sum to zero");
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> (isize, isize, isize) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn moved(&self, dir: HexDirection, n: isize) -> Self {
+        *self + dir.offset() * n
+    }
+
+    pub fn neighbors(&self) -> impl Iterator<Item=Hex> {
+        let hex = *self;
+        HexDirection::all().map(move |dir| hex + dir.offset())
+    }
+
+    /// The number of steps between two hexes
+    pub fn distance(&self, other: Hex) -> isize {
+        let delta = *self - other;
+        (delta.q.abs() + delta.r.abs() + delta.s().abs()) / 2
+    }
+}
+
+#[cfg(test)]
+mod hex_tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        let origin = Hex::new(0, 0);
+        assert!(origin.neighbors().all(|h| origin.distance(h) == 1));
+        assert!(HexDirection::all().all(|d| origin.moved(d, 1) + d.flipped().offset() == origin));
+
+        let hex = origin.moved(HexDirection::NE, 3).moved(HexDirection::SE, 2);
+        assert_eq!(hex.cube(), (3, -1, -2));
+        assert_eq!(Hex::from_cube(3, -1, -2), hex);
+        assert_eq!(origin.distance(hex), 3);
+
+        assert_eq!(HexDirection::NE.rotated_clockwise(), HexDirection::E);
+        assert_eq!(HexDirection::E.rotated_counter_clockwise(), HexDirection::NE);
+        assert_eq!(HexDirection::try_from(Direction::Left), Ok(HexDirection::W));
+        assert_eq!(HexDirection::try_from(Direction8::N), Err(()));
+        assert_eq!(Direction8::from(HexDirection::SW), Direction8::SW);
+    }
+}
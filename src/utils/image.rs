@@ -0,0 +1,69 @@
+use std::io::{self, Write};
+use crate::utils::Grid;
+
+// Writers for the binary Netpbm image formats. Each element of the grid becomes
+// a scale x scale block of pixels.
+impl<T> Grid<T> {
+    /// Writes the grid as a color (P6) image, using a palette function to pick
+    /// the RGB value of each element
+    /// 
+    /// # Examples
+    /// ```
+    /// let file = File::create("day14.ppm").unwrap();
+    /// grid.write_ppm(file, 4, |t| match t {
+    ///     Type::Air => [0, 0, 0],
+    ///     Type::Rock => [128, 128, 128],
+    ///     Type::Sand => [255, 200, 0],
+    /// }).unwrap();
+    /// ```
+    pub fn write_ppm<W: Write, F: Fn(&T) -> [u8; 3]>(&self, writer: W, scale: usize, palette: F) -> io::Result<()> {
+        self.write_netpbm(writer, "P6", scale, |t| palette(t).to_vec())
+    }
+
+    /// Writes the grid as a grayscale (P5) image, using a palette function to pick
+    /// the brightness of each element
+    pub fn write_pgm<W: Write, F: Fn(&T) -> u8>(&self, writer: W, scale: usize, palette: F) -> io::Result<()> {
+        self.write_netpbm(writer, "P5", scale, |t| vec![palette(t)])
+    }
+
+    fn write_netpbm<W: Write, F: Fn(&T) -> Vec<u8>>(&self, writer: W, magic: &str, scale: usize, pixel: F) -> io::Result<()> {
+        assert!(scale > 0, "Image scale must be non-zero");
+
+        let mut writer = io::BufWriter::new(writer);
+        writeln!(writer, "{}\n{} {}\n255", magic, self.x_len() * scale, self.y_len() * scale)?;
+
+        for row in self.iter() {
+            let mut line = vec![];
+            for el in row.iter() {
+                let pixel = pixel(el);
+                for _ in 0..scale {
+                    line.extend_from_slice(&pixel);
+                }
+            }
+
+            for _ in 0..scale {
+                writer.write_all(&line)?;
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod image_tests {
+    use super::*;
+
+    #[test]
+    fn test_write() {
+        let grid = Grid::new(vec![vec![true, false]]);
+
+        let mut ppm = vec![];
+        grid.write_ppm(&mut ppm, 1, |b| if *b { [255, 0, 0] } else { [0, 0, 0] }).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\x00");
+
+        let mut pgm = vec![];
+        grid.write_pgm(&mut pgm, 2, |b| if *b { 255 } else { 0 }).unwrap();
+        assert_eq!(pgm, b"P5\n4 2\n255\n\xff\xff\x00\x00\xff\xff\x00\x00");
+    }
+}
@@ -1,13 +1,48 @@
+// Not every utility is used by a day yet
+#![allow(unused_imports)]
+
+mod aggregate;
+mod automaton;
+mod bit_grid;
+mod cycle;
+mod dimensions;
 mod direction;
+mod draw;
+mod geometry;
 mod grid;
+mod grid3;
+mod hex;
+mod image;
 mod lexer;
 mod misc;
 mod point;
+mod point3;
 mod range;
+mod rect;
+mod region;
+mod render;
+mod sparse_grid;
+mod topology;
+mod transform;
 
+pub use aggregate::*;
+pub use bit_grid::*;
+pub use cycle::*;
+pub use dimensions::*;
 pub use direction::*;
+pub use draw::*;
+pub use geometry::*;
 pub use grid::*;
+pub use grid3::*;
+pub use hex::*;
 pub use lexer::*;
 pub use misc::*;
 pub use point::*;
+pub use point3::*;
 pub use range::*;
+pub use rect::*;
+pub use region::*;
+pub use render::*;
+pub use sparse_grid::*;
+pub use topology::*;
+pub use transform::*;
@@ -2,20 +2,42 @@ extern crate derive_more;
 
 use std::convert::From;
 use derive_more::{Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign};
-use crate::utils::Direction;
+use num::Integer;
+use crate::utils::{neighbor_offsets, Direction, Direction8, Transform2};
 
+/// A 2D point. Coordinates default to isize, which is the only type with the
+/// movement, distance and rotation helpers; other integer types such as usize
+/// indexes get construction and checked conversions between types.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign)]
-pub struct Point {
-    pub x: isize,
-    pub y: isize,
+pub struct Point<T = isize> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
-    pub fn new(x: isize, y: isize) -> Self {
+impl<T: Integer + Copy> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 
+    /// Converts both coordinates to another integer type, or returns None if
+    /// either of them doesn't fit
+    pub fn try_cast<U: TryFrom<T>>(&self) -> Option<Point<U>> {
+        Some(Point {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+        })
+    }
+
+    /// Returns the coordinates as indexes, or None if either is negative or too
+    /// large for a usize
+    pub fn try_into_usize_pair(&self) -> Option<(usize, usize)> where usize: TryFrom<T> {
+        let point = self.try_cast::<usize>()?;
+        Some((point.x, point.y))
+    }
+}
+
+impl Point {
     pub fn moved(&self, dir: Direction, n: isize) -> Self {
         Point {
             x: self.x + dir.dx() * n,
@@ -24,26 +46,122 @@ impl Point {
     }
 
     pub fn unit(&self) -> Self {
-        let mut unit = *self;
+        self.signum()
+    }
 
-        if unit.x < 0 {
-            unit.x = -1;
-        } else if unit.x > 0 {
-            unit.x = 1;
-        }
+    pub fn signum(&self) -> Self {
+        Point::new(self.x.signum(), self.y.signum())
+    }
 
-        if unit.y < 0 {
-            unit.y = -1;
-        } else if unit.y > 0 {
-            unit.y = 1;
-        }
+    pub fn abs(&self) -> Self {
+        Point::new(self.x.abs(), self.y.abs())
+    }
+
+    pub fn dot(&self, other: Point) -> isize {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product, which is positive if other is
+    /// counter-clockwise from self (with y pointing up)
+    pub fn cross(&self, other: Point) -> isize {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn manhattan(&self, other: Point) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn chebyshev(&self, other: Point) -> isize {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn euclidean_sq(&self, other: Point) -> isize {
+        let delta = *self - other;
+        delta.dot(delta)
+    }
 
-        unit
+    /// Whether other is one of the eight points surrounding this one
+    pub fn is_adjacent(&self, other: Point) -> bool {
+        self.chebyshev(other) == 1
+    }
+
+    /// Rotates by 90 degrees clockwise around center, with y pointing down
+    pub fn rotate_cw_around(&self, center: Point) -> Point {
+        Transform2::ROTATE_CW.apply(*self - center) + center
+    }
+
+    /// Rotates by 90 degrees counter-clockwise around center, with y pointing down
+    pub fn rotate_ccw_around(&self, center: Point) -> Point {
+        Transform2::ROTATE_CCW.apply(*self - center) + center
+    }
+
+    /// Mirrors across the vertical line at the given x coordinate
+    pub fn reflect_x(&self, axis: isize) -> Point {
+        Point::new(2 * axis - self.x, self.y)
+    }
+
+    /// Mirrors across the horizontal line at the given y coordinate
+    pub fn reflect_y(&self, axis: isize) -> Point {
+        Point::new(self.x, 2 * axis - self.y)
+    }
+
+    /// Iterates over the four orthogonally adjacent points
+    pub fn neighbors4(&self) -> impl Iterator<Item=Point> {
+        let point = *self;
+        neighbor_offsets::<2>(false).map(move |[dx, dy]| point + Point::new(dx, dy))
+    }
+
+    /// Iterates over the eight surrounding points, including diagonals
+    pub fn neighbors8(&self) -> impl Iterator<Item=Point> {
+        let point = *self;
+        neighbor_offsets::<2>(true).map(move |[dx, dy]| point + Point::new(dx, dy))
+    }
+
+    /// Iterates over the four diagonally adjacent points
+    pub fn diagonals(&self) -> impl Iterator<Item=Point> {
+        let point = *self;
+        Direction8::diagonals().map(move |dir| point + Point::new(dir.dx(), dir.dy()))
     }
 }
 
-impl From<(isize, isize)> for Point {
-    fn from(tuple: (isize, isize)) -> Self {
+impl<T> From<(T, T)> for Point<T> {
+    fn from(tuple: (T, T)) -> Self {
         Point { x: tuple.0, y: tuple.1 }
     }
 }
+
+#[cfg(test)]
+mod point_tests {
+    use super::*;
+
+    #[test]
+    fn test_point() {
+        let a = Point::new(1, -2);
+        let b = Point::new(-3, 4);
+
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(a.euclidean_sq(b), 52);
+        assert_eq!(a.dot(b), -11);
+        assert_eq!(a.cross(b), -2);
+        assert_eq!(b.signum(), Point::new(-1, 1));
+        assert_eq!(b.abs(), Point::new(3, 4));
+
+        assert!(a.is_adjacent(Point::new(2, -1)));
+        assert!(!a.is_adjacent(a));
+        assert!(a.neighbors8().all(|p| a.is_adjacent(p)));
+        assert_eq!(a.neighbors4().filter(|p| a.manhattan(*p) == 1).count(), 4);
+        assert_eq!(a.rotate_cw_around(Point::new(0, 0)), Point::new(2, 1));
+        assert_eq!(a.rotate_ccw_around(Point::new(1, 1)), Point::new(-2, 1));
+        assert_eq!(a.rotate_cw_around(b).rotate_ccw_around(b), a);
+        assert_eq!(a.reflect_x(3), Point::new(5, -2));
+        assert_eq!(a.reflect_y(0), Point::new(1, 2));
+
+        assert_eq!(a.try_into_usize_pair(), None);
+        assert_eq!(b.abs().try_into_usize_pair(), Some((3, 4)));
+        assert_eq!(Point::<usize>::new(3, 4).try_cast::<isize>(), Some(Point::new(3, 4)));
+        assert_eq!(Point::<usize>::new(usize::MAX, 0).try_cast::<isize>(), None);
+
+        assert_eq!(a.diagonals().filter(|p| a.chebyshev(*p) == 1 && a.manhattan(*p) == 2).count(), 4);
+    }
+}
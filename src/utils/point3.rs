@@ -0,0 +1,57 @@
+extern crate derive_more;
+
+use std::convert::From;
+use derive_more::{Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign};
+use crate::utils::neighbor_offsets;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point3 {
+    pub fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn unit(&self) -> Self {
+        Point3 {
+            x: self.x.signum(),
+            y: self.y.signum(),
+            z: self.z.signum(),
+        }
+    }
+
+    /// Iterates over the six points that share a face with this one
+    pub fn neighbors6(&self) -> impl Iterator<Item=Point3> {
+        let point = *self;
+        neighbor_offsets::<3>(false).map(move |offset| point + Point3::from(offset))
+    }
+
+    /// Iterates over the 26 points that share a face, edge or corner with this one
+    pub fn neighbors26(&self) -> impl Iterator<Item=Point3> {
+        let point = *self;
+        neighbor_offsets::<3>(true).map(move |offset| point + Point3::from(offset))
+    }
+}
+
+impl From<(isize, isize, isize)> for Point3 {
+    fn from(tuple: (isize, isize, isize)) -> Self {
+        Point3 { x: tuple.0, y: tuple.1, z: tuple.2 }
+    }
+}
+
+impl From<[isize; 3]> for Point3 {
+    fn from(array: [isize; 3]) -> Self {
+        Point3 { x: array[0], y: array[1], z: array[2] }
+    }
+}
+
+impl From<Point3> for [isize; 3] {
+    fn from(point: Point3) -> Self {
+        [point.x, point.y, point.z]
+    }
+}
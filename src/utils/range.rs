@@ -1,7 +1,8 @@
+use std::collections::BTreeMap;
 use std::ops::AddAssign;
 use std::cmp::PartialOrd;
 use std::fmt::Debug;
-use num::Signed;
+use num::{NumCast, Signed};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Range<T : Clone + Copy + Debug> {
@@ -10,12 +11,8 @@ pub struct Range<T : Clone + Copy + Debug> {
     step: T,
 }
 
-fn difference_mod<T : Signed + PartialOrd + AddAssign + Copy + Debug>(a: T, b: T, c: T) -> T {
-    ((a % c) - (b % c)) % c
-}
-
 #[allow(dead_code)]
-impl<T : Signed + PartialOrd + AddAssign + Copy + Debug> Range<T> {
+impl<T : Signed + PartialOrd + AddAssign + Copy + Debug + NumCast> Range<T> {
     fn new(start: T, end_inclusive: T, step: T) -> Range<T> {
         assert!(step != T::zero(), "Range step must be non-zero");
         Range { start, end_inclusive, step }
@@ -48,29 +45,148 @@ impl<T : Signed + PartialOrd + AddAssign + Copy + Debug> Range<T> {
         Self::new(start, stop, step)
     }
 
+    /// The number of elements. Panics if there are more than usize::MAX.
     pub fn len(&self) -> usize {
-        // TODO: Calculate this
-        self.iter().collect::<Vec<_>>().len()
+        let span = wide(self.end_inclusive) - wide(self.start);
+        let step = wide(self.step);
+        if span != 0 && span.signum() != step.signum() {
+            return 0;
+        }
+
+        usize::try_from(span / step).ok()
+            .and_then(|steps| steps.checked_add(1))
+            .expect("Range has more than usize::MAX elements")
     }
 
-    pub fn iter(&self) -> RangeIterator<T> {
-        let last =  if self.step > T::zero() {
-            if self.start >= self.end_inclusive {
-                self.end_inclusive
-            } else {
-                self.end_inclusive - difference_mod(self.end_inclusive, self.start, self.step)
-            }
-        } else if self.step < T::zero() {
-            if self.start <= self.end_inclusive {
-                self.end_inclusive
-            } else {
-                self.end_inclusive + difference_mod(self.start, self.end_inclusive, -self.step)
-            }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the nth element of the range without iterating over it
+    pub fn nth(&self, n: usize) -> Option<T> {
+        if n < self.len() {
+            Some(step_from(self.start, self.step, n))
         } else {
-            unreachable!();
+            None
+        }
+    }
+
+    pub fn first(&self) -> Option<T> {
+        self.nth(0)
+    }
+
+    pub fn last(&self) -> Option<T> {
+        self.len().checked_sub(1).and_then(|n| self.nth(n))
+    }
+
+    /// Whether the value is one of the elements of the range, i.e. it lies between
+    /// the first and last elements and is a whole number of steps from the start
+    pub fn contains(&self, value: T) -> bool {
+        let last = match self.last() {
+            Some(last) => last,
+            None => return false,
         };
 
-        RangeIterator::new(self.start, last, self.step)
+        let in_bounds = if self.step > T::zero() {
+            self.start <= value && value <= last
+        } else {
+            last <= value && value <= self.start
+        };
+
+        in_bounds && (wide(value) - wide(self.start)) % wide(self.step) == 0
+    }
+
+    /// Splits the range into its first n elements and the rest, keeping the step.
+    /// Panics if n is greater than the length.
+    pub fn split_at(&self, n: usize) -> (Range<T>, Range<T>) {
+        assert!(n <= self.len(), "Split index {} is out of bounds", n);
+
+        let split = step_from(self.start, self.step, n);
+        (
+            Range { start: self.start, end_inclusive: split - self.step, step: self.step },
+            Range { start: split, end_inclusive: self.end_inclusive, step: self.step },
+        )
+    }
+
+    // The following treat a range as every value between its lowest and highest
+    // elements, regardless of step.
+
+    /// Compares the spans of two ranges using Allen's interval algebra, or returns
+    /// None if either is empty. Ranges that end right before the other starts, like
+    /// 1..=3 and 4..=6, meet.
+    pub fn relation(&self, other: &Range<T>) -> Option<IntervalRelation> {
+        let (a0, a1) = self.span()?;
+        let (b0, b1) = other.span()?;
+
+        // Only add one to the lower value, so this can't overflow at T::MAX
+        let relation = if a1 < b0 {
+            if a1 + T::one() == b0 { IntervalRelation::Meets } else { IntervalRelation::Before }
+        } else if b1 < a0 {
+            if b1 + T::one() == a0 { IntervalRelation::MetBy } else { IntervalRelation::After }
+        } else if a0 == b0 && a1 == b1 {
+            IntervalRelation::Equal
+        } else if a0 == b0 {
+            if a1 < b1 { IntervalRelation::Starts } else { IntervalRelation::StartedBy }
+        } else if a1 == b1 {
+            if a0 > b0 { IntervalRelation::Finishes } else { IntervalRelation::FinishedBy }
+        } else if a0 > b0 && a1 < b1 {
+            IntervalRelation::During
+        } else if a0 < b0 && a1 > b1 {
+            IntervalRelation::Contains
+        } else if a0 < b0 {
+            IntervalRelation::Overlaps
+        } else {
+            IntervalRelation::OverlappedBy
+        };
+
+        Some(relation)
+    }
+
+    /// Whether the span of other is within the span of this range, ignoring step.
+    /// False if either is empty.
+    pub fn contains_range(&self, other: &Range<T>) -> bool {
+        matches!(
+            self.relation(other),
+            Some(IntervalRelation::Equal | IntervalRelation::Contains | IntervalRelation::StartedBy | IntervalRelation::FinishedBy)
+        )
+    }
+
+    /// Whether the spans of the ranges share at least one value, ignoring step
+    pub fn overlaps(&self, other: &Range<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Whether the ranges don't overlap, but one starts right after the other ends
+    pub fn adjacent_to(&self, other: &Range<T>) -> bool {
+        matches!(self.relation(other), Some(IntervalRelation::Meets | IntervalRelation::MetBy))
+    }
+
+    /// Returns the values shared by the spans of both ranges, ignoring step, as an
+    /// ascending range with a step of 1, or None if they don't overlap
+    pub fn intersection(&self, other: &Range<T>) -> Option<Range<T>> {
+        let (a0, a1) = self.span()?;
+        let (b0, b1) = other.span()?;
+        let (start, end) = (max_of(a0, b0), min_of(a1, b1));
+
+        if start <= end {
+            Some(Range::new(start, end, T::one()))
+        } else {
+            None
+        }
+    }
+
+    // The lowest and highest elements, or None if the range is empty
+    fn span(&self) -> Option<(T, T)> {
+        let (first, last) = (self.first()?, self.last()?);
+        Some((min_of(first, last), max_of(first, last)))
+    }
+
+    pub fn iter(&self) -> RangeIterator<T> {
+        RangeIterator {
+            step: self.step,
+            next: self.start,
+            remaining: self.len(),
+        }
     }
 
     fn get_implicit_step_for(start: T, stop: T) -> T {
@@ -82,56 +198,342 @@ impl<T : Signed + PartialOrd + AddAssign + Copy + Debug> Range<T> {
     }
 }
 
+// Converts to i128, where the difference of any two values of a narrower integer
+// type fits, so ranges wider than T::MAX like i64::MIN..=0 don't overflow
+fn wide<T : NumCast + Copy + Debug>(value: T) -> i128 {
+    value.to_i128().unwrap_or_else(|| panic!("{:?} doesn't fit in an i128", value))
+}
+
+// The value n steps from start, which must fit in T even if step * n doesn't
+fn step_from<T : NumCast + Copy + Debug>(start: T, step: T, n: usize) -> T {
+    T::from(wide(start) + wide(step) * n as i128).expect("Range element is out of bounds")
+}
+
+// T is only PartialOrd, so std's min and max aren't available
+fn min_of<T : PartialOrd>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+fn max_of<T : PartialOrd>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+/// How two intervals are positioned relative to each other, from Allen's
+/// interval algebra. Each variant reads as "self ... other".
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IntervalRelation {
+    /// Self ends before other starts, with a gap between them
+    Before,
+    /// Self ends right before other starts
+    Meets,
+    /// Self starts first and ends inside other
+    Overlaps,
+    /// Both start together and self ends first
+    Starts,
+    /// Self is strictly inside other
+    During,
+    /// Both end together and self starts last
+    Finishes,
+    Equal,
+    FinishedBy,
+    Contains,
+    StartedBy,
+    OverlappedBy,
+    MetBy,
+    After,
+}
+
+impl IntervalRelation {
+    /// The relation with self and other swapped
+    pub fn inverse(&self) -> IntervalRelation {
+        match self {
+            IntervalRelation::Before => IntervalRelation::After,
+            IntervalRelation::Meets => IntervalRelation::MetBy,
+            IntervalRelation::Overlaps => IntervalRelation::OverlappedBy,
+            IntervalRelation::Starts => IntervalRelation::StartedBy,
+            IntervalRelation::During => IntervalRelation::Contains,
+            IntervalRelation::Finishes => IntervalRelation::FinishedBy,
+            IntervalRelation::Equal => IntervalRelation::Equal,
+            IntervalRelation::FinishedBy => IntervalRelation::Finishes,
+            IntervalRelation::Contains => IntervalRelation::During,
+            IntervalRelation::StartedBy => IntervalRelation::Starts,
+            IntervalRelation::OverlappedBy => IntervalRelation::Overlaps,
+            IntervalRelation::MetBy => IntervalRelation::Meets,
+            IntervalRelation::After => IntervalRelation::Before,
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct RangeIterator<T : Clone + Copy> {
     step: T,
-    final_element: T,
-    has_next: bool,
     next: T,
+    remaining: usize,
 }
 
-impl<T : Signed + PartialOrd + AddAssign + Copy + Debug> RangeIterator<T> {
-    fn new(first: T, last: T, step: T) -> Self {
-        let has_next = if step > T::zero() { 
-            first <= last
-        } else {
-            first >= last
-        };
+impl<T : Signed + PartialOrd + AddAssign + Copy + Debug + NumCast> Iterator for RangeIterator<T> {
+    type Item = T;
 
-        RangeIterator {
-            step,
-            final_element: last,
-            has_next,
-            next: if has_next { first } else { last },
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let value = self.next;
+        self.remaining -= 1;
+
+        // Don't step past the last element, which could overflow
+        if self.remaining > 0 {
+            self.next += self.step;
         }
+
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+
+        self.next = step_from(self.next, self.step, n);
+        self.remaining -= n;
+        self.next()
     }
 }
 
-impl<T : Signed + PartialOrd + AddAssign + Copy + Debug> Iterator for RangeIterator<T> {
+impl<T : Signed + PartialOrd + AddAssign + Copy + Debug + NumCast> DoubleEndedIterator for RangeIterator<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+        Some(step_from(self.next, self.step, self.remaining))
+    }
+}
+
+impl<T : Signed + PartialOrd + AddAssign + Copy + Debug + NumCast> ExactSizeIterator for RangeIterator<T> {}
+
+impl<T : Signed + PartialOrd + AddAssign + Copy + Debug + NumCast> IntoIterator for Range<T> {
     type Item = T;
+    type IntoIter = RangeIterator<T>;
 
-    fn next(&mut self) -> Option<T> {
-        if !self.has_next {
-            None
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A set of integers stored as sorted, disjoint intervals
+///
+/// Overlapping and adjacent intervals are merged as they're inserted, so the size
+/// of the set depends on the number of separate intervals rather than the number
+/// of values covered. Insertions and removals take logarithmic time in the number
+/// of intervals, and the binary set operations are linear.
+///
+/// # Examples
+/// ```
+/// let mut covered = IntervalSet::new();
+/// covered.insert(Range::inclusive(-2, 2));
+/// covered.insert(Range::inclusive(12, 12));
+/// covered.insert(Range::inclusive(3, 7));
+///
+/// assert_eq!(covered.covered_len(), 11);
+/// assert_eq!(covered.gaps().map(|gap| gap.len()).collect::<Vec<_>>(), vec![4]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T : Clone + Copy + Debug> {
+    // Maps the start of each interval to its inclusive end
+    intervals: BTreeMap<T, T>,
+}
+
+impl<T : Signed + Ord + AddAssign + Copy + Debug + NumCast> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { intervals: BTreeMap::new() }
+    }
+
+    /// Adds every value in the range. Ranges must have a step of 1 or -1.
+    pub fn insert(&mut self, range: Range<T>) {
+        if let Some((start, end)) = Self::bounds_of(range) {
+            self.insert_bounds(start, end);
+        }
+    }
+
+    /// Removes every value in the range. Ranges must have a step of 1 or -1.
+    pub fn remove(&mut self, range: Range<T>) {
+        if let Some((start, end)) = Self::bounds_of(range) {
+            self.remove_bounds(start, end);
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        matches!(self.intervals.range(..=value).next_back(), Some((_, end)) if *end >= value)
+    }
+
+    /// The number of separate intervals in the set
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values in the set
+    pub fn covered_len(&self) -> usize {
+        self.intervals.iter().map(|(start, end)| usize::try_from(wide(*end) - wide(*start) + 1).unwrap()).sum()
+    }
+
+    /// Iterates over the intervals of the set in ascending order
+    pub fn ranges(&self) -> impl Iterator<Item=Range<T>> + '_ {
+        self.intervals.iter().map(|(start, end)| Range::inclusive(*start, *end))
+    }
+
+    /// Iterates over the intervals between consecutive intervals of the set
+    pub fn gaps(&self) -> impl Iterator<Item=Range<T>> + '_ {
+        self.intervals.iter().zip(self.intervals.iter().skip(1)).map(|((_, end), (next_start, _))| {
+            Range::inclusive(*end + T::one(), *next_start - T::one())
+        })
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let (mut result, smaller) = if self.interval_count() >= other.interval_count() {
+            (self.clone(), other)
         } else {
-            let value = self.next;
-            if value == self.final_element {
-                self.has_next = false;
+            (other.clone(), self)
+        };
+
+        for (start, end) in smaller.intervals.iter() {
+            result.insert_bounds(*start, *end);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = IntervalSet::new();
+        let mut a = self.intervals.iter().peekable();
+        let mut b = other.intervals.iter().peekable();
+
+        while let (Some((a_start, a_end)), Some((b_start, b_end))) = (a.peek(), b.peek()) {
+            let start = **a_start.max(b_start);
+            let end = **a_end.min(b_end);
+            if start <= end {
+                result.intervals.insert(start, end);
+            }
+
+            // Whichever interval ends first can't overlap anything else
+            if a_end < b_end {
+                a.next();
             } else {
-                self.next += self.step;
+                b.next();
+            }
+        }
+
+        result
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        for (start, end) in other.intervals.iter() {
+            result.remove_bounds(*start, *end);
+        }
+        result
+    }
+
+    /// Returns every value inside the bounds that isn't in the set
+    pub fn complement(&self, bounds: Range<T>) -> IntervalSet<T> {
+        let mut result = IntervalSet::new();
+        result.insert(bounds);
+        result.difference(self)
+    }
+
+    fn insert_bounds(&mut self, mut start: T, mut end: T) {
+        // Merge with an interval that starts before this one and overlaps or touches
+        // it. Adjacency is checked by adding to the lower value, which can't overflow,
+        // since start - 1 would at T::MIN.
+        if let Some((prev_start, prev_end)) = self.intervals.range(..=start).next_back() {
+            if *prev_end >= start || *prev_end + T::one() == start {
+                start = *prev_start;
+                end = end.max(*prev_end);
+            }
+        }
+
+        // Merge with every interval that starts inside this one or right after it
+        let merged = self.intervals.range(start..)
+            .take_while(|(next_start, _)| **next_start <= end || end + T::one() == **next_start)
+            .map(|(next_start, next_end)| (*next_start, *next_end))
+            .collect::<Vec<_>>();
+
+        for (next_start, next_end) in merged {
+            end = end.max(next_end);
+            self.intervals.remove(&next_start);
+        }
+
+        self.intervals.insert(start, end);
+    }
+
+    fn remove_bounds(&mut self, start: T, end: T) {
+        let mut overlapping = self.intervals.range(start..=end)
+            .map(|(s, e)| (*s, *e))
+            .collect::<Vec<_>>();
+
+        if let Some((prev_start, prev_end)) = self.intervals.range(..start).next_back() {
+            if *prev_end >= start {
+                overlapping.push((*prev_start, *prev_end));
             }
+        }
 
-            Some(value)
+        // Only step past start and end when an interval extends beyond them, so
+        // neither can be T::MIN or T::MAX
+        for (interval_start, interval_end) in overlapping {
+            self.intervals.remove(&interval_start);
+            if interval_start < start {
+                self.intervals.insert(interval_start, start - T::one());
+            }
+            if interval_end > end {
+                self.intervals.insert(end + T::one(), interval_end);
+            }
         }
     }
+
+    // Returns the lowest and highest values of a range with a step of 1 or -1
+    fn bounds_of(range: Range<T>) -> Option<(T, T)> {
+        assert!(range.step.abs() == T::one(), "IntervalSet ranges must have a step of 1 or -1");
+
+        // Not using span, since ranges covering all of T are too long for len
+        let (low, high) = if range.step > T::zero() {
+            (range.start, range.end_inclusive)
+        } else {
+            (range.end_inclusive, range.start)
+        };
+
+        if low <= high { Some((low, high)) } else { None }
+    }
 }
 
-impl<T: Signed + PartialOrd + AddAssign + Copy + Debug> IntoIterator for Range<T> {
-    type Item = T;
-    type IntoIter = RangeIterator<T>;
+impl<T : Signed + Ord + AddAssign + Copy + Debug + NumCast> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+impl<T : Signed + Ord + AddAssign + Copy + Debug + NumCast> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item=Range<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T : Signed + Ord + AddAssign + Copy + Debug + NumCast> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item=Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
     }
 }
 
@@ -164,5 +566,151 @@ mod range_tests {
 
         assert_eq!(Range::inclusive(0, 0).len(), 1);
         assert_eq!(Range::exclusive(0, 0).len(), 0);
+
+        assert_eq!(Range::inclusive_stepped(1, 4, 2).len(), 2);
+        assert_eq!(Range::inclusive_stepped(-1, -4, -2).len(), 2);
+        assert!(Range::exclusive(3, 3).is_empty());
+        assert!(!Range::inclusive(3, 3).is_empty());
+    }
+
+    #[test]
+    fn test_elements() {
+        let range = Range::inclusive_stepped(1, 10, 3);
+        assert_eq!(range.first(), Some(1));
+        assert_eq!(range.last(), Some(10));
+        assert_eq!(range.nth(2), Some(7));
+        assert_eq!(range.nth(4), None);
+        assert!(range.contains(4) && range.contains(10));
+        assert!(!range.contains(5) && !range.contains(13) && !range.contains(-2));
+
+        let range = Range::exclusive_stepped(0, -7, -3);
+        assert_eq!(range.last(), Some(-6));
+        assert!(range.contains(-3) && !range.contains(-7) && !range.contains(3));
+
+        let empty = Range::exclusive(0, 0);
+        assert_eq!((empty.first(), empty.last()), (None, None));
+        assert!(!empty.contains(0));
+
+        let wide = Range::inclusive(i64::MIN, 0);
+        assert_eq!(wide.len(), (1 << 63) + 1);
+        assert_eq!((wide.first(), wide.last()), (Some(i64::MIN), Some(0)));
+        assert_eq!(wide.nth(1 << 62), Some(-(1 << 62)));
+        assert!(wide.contains(-5) && !wide.contains(1));
+        assert!(Range::inclusive_stepped(i8::MAX, i8::MIN, -5).contains(-3));
+        assert_eq!(Range::inclusive(i8::MIN, i8::MAX).iter().rev().nth(255), Some(i8::MIN));
+    }
+
+    #[test]
+    fn test_iterator() {
+        let range = Range::inclusive_stepped(1, 10, 3);
+        assert_eq!(range.iter().collect::<Vec<_>>(), vec![1, 4, 7, 10]);
+        assert_eq!(range.iter().rev().collect::<Vec<_>>(), vec![10, 7, 4, 1]);
+        assert_eq!(Range::exclusive(5, 0).iter().rev().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+
+        let mut iter = range.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(10));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.nth(1), Some(7));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        assert_eq!(Range::inclusive(i8::MAX - 2, i8::MAX).iter().collect::<Vec<_>>(), vec![125, 126, 127]);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let mut set: IntervalSet<i64> = [Range::inclusive(-2, 2), Range::inclusive(12, 12), Range::inclusive(7, 3)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(set.interval_count(), 2);
+        assert_eq!(set.covered_len(), 11);
+        assert_eq!(set.gaps().collect::<Vec<_>>().len(), 1);
+        assert_eq!(set.gaps().next().unwrap().iter().collect::<Vec<_>>(), vec![8, 9, 10, 11]);
+        assert!(set.contains(-2) && set.contains(7) && set.contains(12));
+        assert!(!set.contains(8) && !set.contains(13) && !set.contains(-3));
+
+        set.insert(Range::inclusive(8, 11));
+        assert_eq!(set.interval_count(), 1);
+        assert_eq!(set.covered_len(), 15);
+
+        set.remove(Range::exclusive(0, 5));
+        set.remove(Range::inclusive(12, 20));
+        assert_eq!(set.ranges().map(|r| (r.first().unwrap(), r.last().unwrap())).collect::<Vec<_>>(), vec![(-2, -1), (5, 11)]);
+
+        let other: IntervalSet<i64> = [Range::inclusive(-10, -2), Range::inclusive(4, 6), Range::inclusive(10, 30)].into_iter().collect();
+        let bounds = |s: &IntervalSet<i64>| s.ranges().map(|r| (r.first().unwrap(), r.last().unwrap())).collect::<Vec<_>>();
+
+        assert_eq!(bounds(&set.union(&other)), vec![(-10, -1), (4, 30)]);
+        assert_eq!(bounds(&set.intersection(&other)), vec![(-2, -2), (5, 6), (10, 11)]);
+        assert_eq!(bounds(&set.difference(&other)), vec![(-1, -1), (7, 9)]);
+        assert_eq!(bounds(&set.complement(Range::inclusive(-5, 20))), vec![(-5, -3), (0, 4), (12, 20)]);
+
+        let mut edges: IntervalSet<i64> = [Range::inclusive(i64::MIN, -10), Range::inclusive(i64::MAX, 10)].into_iter().collect();
+        edges.insert(Range::inclusive(-9, 9));
+        assert_eq!(edges.interval_count(), 1);
+        assert!(edges.contains(i64::MIN) && edges.contains(i64::MAX));
+        edges.remove(Range::inclusive(i64::MIN, i64::MIN + 1));
+        edges.remove(Range::inclusive(i64::MAX, i64::MAX));
+        edges.remove(Range::inclusive(0, 0));
+        assert_eq!(bounds(&edges), vec![(i64::MIN + 2, -1), (1, i64::MAX - 1)]);
+        assert_eq!(bounds(&edges.complement(Range::inclusive(i64::MIN, 5))), vec![(i64::MIN, i64::MIN + 1), (0, 0)]);
+        assert_eq!(IntervalSet::new().complement(Range::inclusive(i8::MIN, i8::MAX)).covered_len(), 256);
+
+        let huge: IntervalSet<i64> = (0..100_000i64).rev().map(|i| Range::inclusive(i * 3_000_000, i * 3_000_000 + 1_999_999)).collect();
+        assert_eq!(huge.interval_count(), 100_000);
+        assert_eq!(huge.covered_len(), 200_000_000_000);
+        assert_eq!(huge.complement(Range::inclusive(0, 299_999_999_999)).covered_len(), 100_000_000_000);
+    }
+
+    #[test]
+    fn test_relations() {
+        let a = Range::inclusive(2, 6);
+        let relation = |start, end| a.relation(&Range::inclusive(start, end)).unwrap();
+
+        assert_eq!(relation(8, 10), IntervalRelation::Before);
+        assert_eq!(relation(7, 10), IntervalRelation::Meets);
+        assert_eq!(relation(4, 10), IntervalRelation::Overlaps);
+        assert_eq!(relation(2, 10), IntervalRelation::Starts);
+        assert_eq!(relation(0, 10), IntervalRelation::During);
+        assert_eq!(relation(0, 6), IntervalRelation::Finishes);
+        assert_eq!(relation(6, 2), IntervalRelation::Equal);
+        assert_eq!(relation(4, 6), IntervalRelation::FinishedBy);
+        assert_eq!(relation(3, 5), IntervalRelation::Contains);
+        assert_eq!(relation(2, 4), IntervalRelation::StartedBy);
+        assert_eq!(relation(0, 4), IntervalRelation::OverlappedBy);
+        assert_eq!(relation(0, 1), IntervalRelation::MetBy);
+        assert_eq!(relation(-3, 0), IntervalRelation::After);
+
+        for (start, end) in [(8, 10), (7, 10), (4, 10), (2, 4), (3, 5), (0, 6), (-3, 0)] {
+            let b = Range::inclusive(start, end);
+            assert_eq!(b.relation(&a), a.relation(&b).map(|r| r.inverse()));
+        }
+        assert_eq!(a.relation(&Range::exclusive(3, 3)), None);
+
+        let max = Range::inclusive(0i32, i32::MAX);
+        assert!(max.contains_range(&Range::inclusive(5, 10)) && max.overlaps(&Range::inclusive(i32::MAX, 10)));
+        assert_eq!(Range::inclusive(i32::MIN, -1).relation(&max), Some(IntervalRelation::Meets));
+        assert_eq!(Range::inclusive(i32::MIN, -5).relation(&max), Some(IntervalRelation::Before));
+        assert_eq!(max.relation(&Range::inclusive(i32::MIN, i32::MIN)), Some(IntervalRelation::After));
+
+        let stepped = Range::inclusive_stepped(0, 10, 5);
+        assert!(stepped.contains_range(&Range::inclusive(1, 4)) && stepped.overlaps(&Range::inclusive(1, 4)));
+
+        assert!(a.contains_range(&Range::inclusive(3, 6)) && !a.contains_range(&Range::inclusive(3, 7)));
+        assert!(a.overlaps(&Range::inclusive(6, 9)) && !a.overlaps(&Range::inclusive(7, 9)));
+        assert!(a.adjacent_to(&Range::inclusive(7, 9)) && !a.adjacent_to(&Range::inclusive(6, 9)));
+        assert!(!a.overlaps(&Range::exclusive(3, 3)) && !a.contains_range(&Range::exclusive(3, 3)));
+
+        let intersection = a.intersection(&Range::exclusive(10, 4)).unwrap();
+        assert_eq!(intersection.iter().collect::<Vec<_>>(), vec![5, 6]);
+        assert!(a.intersection(&Range::inclusive(7, 9)).is_none());
+
+        let (first, rest) = Range::inclusive_stepped(10, 0, -3).split_at(2);
+        assert_eq!(first.iter().collect::<Vec<_>>(), vec![10, 7]);
+        assert_eq!(rest.iter().collect::<Vec<_>>(), vec![4, 1]);
+        assert!(a.split_at(0).0.is_empty() && a.split_at(5).1.is_empty());
     }
 }
@@ -0,0 +1,123 @@
+use crate::utils::Point;
+
+/// An axis-aligned rectangle of points, with inclusive corners
+///
+/// A rectangle whose max is less than its min on either axis is empty. Empty
+/// rectangles contain no points, and are ignored by union.
+///
+/// # Examples
+/// ```
+/// let bounds = Rect::from_points(points.iter().copied()).unwrap();
+/// let grid = Grid::new(vec![vec!['.'; bounds.width()]; bounds.height()])
+///     .with_origin(bounds.min);
+///
+/// for point in bounds.expand(1).points() {
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    /// The smallest rectangle containing every point, or None if there are none
+    pub fn from_points<I: IntoIterator<Item=Point>>(points: I) -> Option<Rect> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Rect::new(first, first), |rect, point| rect.including(point)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max.x < self.min.x || self.max.y < self.min.y
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1).max(0) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Returns the overlapping part of both rectangles, or None if they don't overlap
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect::new(
+            Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        );
+
+        if rect.is_empty() { None } else { Some(rect) }
+    }
+
+    /// The smallest rectangle containing both rectangles
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        Rect::new(
+            Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    /// The smallest rectangle containing this one and the given point
+    pub fn including(&self, point: Point) -> Rect {
+        self.union(&Rect::new(point, point))
+    }
+
+    /// Grows the rectangle by n on every side. A negative n shrinks it.
+    pub fn expand(&self, n: isize) -> Rect {
+        Rect::new(self.min - Point::new(n, n), self.max + Point::new(n, n))
+    }
+
+    /// Iterates over every point inside the rectangle in row-major order
+    pub fn points(&self) -> impl Iterator<Item=Point> {
+        let rect = *self;
+        (rect.min.y..=rect.max.y).flat_map(move |y| (rect.min.x..=rect.max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod rect_tests {
+    use super::*;
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::from_points([Point::new(3, -1), Point::new(0, 2), Point::new(1, 1)]).unwrap();
+        assert_eq!(rect, Rect::new(Point::new(0, -1), Point::new(3, 2)));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (4, 4, 16));
+        assert!(rect.contains(Point::new(3, 2)) && !rect.contains(Point::new(4, 0)));
+        assert_eq!(Rect::from_points([]), None);
+
+        let other = Rect::new(Point::new(2, 1), Point::new(5, 5));
+        assert_eq!(rect.intersection(&other), Some(Rect::new(Point::new(2, 1), Point::new(3, 2))));
+        assert_eq!(rect.union(&other), Rect::new(Point::new(0, -1), Point::new(5, 5)));
+        assert_eq!(rect.intersection(&Rect::new(Point::new(4, 0), Point::new(5, 0))), None);
+
+        assert_eq!(rect.expand(1).area(), 36);
+        assert!(rect.expand(-2).is_empty());
+        assert_eq!(rect.expand(-2).area(), 0);
+        assert_eq!(rect.expand(-2).points().count(), 0);
+        assert_eq!(rect.points().count(), rect.area());
+        assert_eq!(rect.points().nth(5), Some(Point::new(1, 0)));
+    }
+}
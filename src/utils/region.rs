@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use crate::utils::{Grid, GridNode, Point, Rect};
+
+/// The result of labelling the connected regions of a Grid
+pub struct Components {
+    /// A grid of the same size as the original, holding the index into regions of
+    /// the region each element belongs to
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub size: usize,
+    /// The bounding box of the region, in world coordinates
+    pub bounds: Rect,
+}
+
+impl<T> Grid<T> {
+    /// Finds every node that can be reached from start by moving between orthogonal
+    /// neighbors that satisfy the predicate. The result is empty if the start node
+    /// itself doesn't satisfy it.
+    pub fn flood_fill<'a, P: Fn(&T) -> bool>(&'a self, start: GridNode<'a, T>, predicate: P) -> HashSet<GridNode<'a, T>> {
+        let mut visited = HashSet::new();
+        if !predicate(&start) {
+            return visited;
+        }
+
+        let mut to_process = vec![start];
+        visited.insert(start);
+
+        while let Some(node) = to_process.pop() {
+            for neighbor in node.neighbors() {
+                if predicate(&neighbor) && visited.insert(neighbor) {
+                    to_process.push(neighbor);
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// Splits the grid into connected regions. Two orthogonal neighbors are in the
+    /// same region if same_region returns true for their values. Regions are
+    /// numbered in the order their first element appears in row-major order.
+    pub fn components<F: Fn(&T, &T) -> bool>(&self, same_region: F) -> Components {
+        let mut labels: Vec<Vec<Option<usize>>> = vec![vec![None; self.x_len()]; self.y_len()];
+        let mut regions = vec![];
+
+        for start in self.nodes() {
+            if labels[start.y()][start.x()].is_some() {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = Region { size: 0, bounds: Rect::new(start.point(), start.point()) };
+
+            let mut to_process = vec![start];
+            labels[start.y()][start.x()] = Some(label);
+
+            while let Some(node) = to_process.pop() {
+                region.size += 1;
+                region.bounds = region.bounds.including(node.point());
+
+                for neighbor in node.neighbors() {
+                    if labels[neighbor.y()][neighbor.x()].is_none() && same_region(&node, &neighbor) {
+                        labels[neighbor.y()][neighbor.x()] = Some(label);
+                        to_process.push(neighbor);
+                    }
+                }
+            }
+
+            regions.push(region);
+        }
+
+        Components {
+            labels: Grid::new(labels.into_iter().map(|row| row.into_iter().map(Option::unwrap).collect()).collect())
+                .with_origin(self.origin()),
+            regions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod region_tests {
+    use super::*;
+
+    #[test]
+    fn test_regions() {
+        let grid = Grid::new(vec![
+            "aab".chars().collect(),
+            "bbb".chars().collect(),
+            "aba".chars().collect(),
+        ]);
+
+        let filled = grid.flood_fill(grid.at(0, 0).unwrap(), |c| *c == 'a');
+        assert_eq!(filled.len(), 2);
+        assert!(grid.flood_fill(grid.at(2, 0).unwrap(), |c| *c == 'a').is_empty());
+
+        let components = grid.components(|a, b| a == b);
+        assert_eq!(components.regions.len(), 4);
+        assert_eq!(components.regions[1], Region { size: 5, bounds: Rect::new(Point::new(0, 0), Point::new(2, 2)) });
+        assert_eq!(components.regions[3], Region { size: 1, bounds: Rect::new(Point::new(2, 2), Point::new(2, 2)) });
+        assert_eq!(*components.labels.at(1, 2).unwrap(), 1);
+        assert_eq!(*components.labels.at(0, 2).unwrap(), 2);
+
+        let grid = grid.with_origin(Point::new(-5, 3));
+        let components = grid.components(|a, b| a == b);
+        let region = components.regions[3];
+        assert_eq!(region.bounds.min, Point::new(-3, 5));
+        assert_eq!(*components.labels.at_point(region.bounds.min).unwrap(), 3);
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::fmt;
+use crate::utils::{Grid, Point, Rect};
+
+/// Types which can be drawn as a single character in a rendered Grid
+pub trait GridChar {
+    fn grid_char(&self) -> char;
+}
+
+impl GridChar for char {
+    fn grid_char(&self) -> char {
+        *self
+    }
+}
+
+impl GridChar for bool {
+    fn grid_char(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi_code(&self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+/// A configurable text rendering of a Grid.
+///
+/// Every element is drawn as the character returned by the mapping function.
+/// The output can optionally be labelled with coordinate axes, have individual
+/// points drawn in color, and be cropped to a rectangle of the grid. The result
+/// is written out via its Display implementation.
+///
+/// # Examples
+/// ```
+/// let rendered = grid.render_with(|height| (b'a' + *height as u8) as char)
+///     .with_axes()
+///     .highlight(path, Color::Red)
+///     .crop(Rect::new(Point::new(10, 0), Point::new(49, 19)));
+///
+/// println!("{}", rendered);
+/// ```
+pub struct GridRenderer<'a, T, F> {
+    grid: &'a Grid<T>,
+    char_fn: F,
+    axes: bool,
+    highlights: HashMap<Point, Color>,
+    // The part of the grid to render in world coordinates, or None if empty
+    crop: Option<Rect>,
+}
+
+impl<T> Grid<T> {
+    pub fn render(&self) -> GridRenderer<'_, T, fn(&T) -> char> where T: GridChar {
+        GridRenderer::new(self, T::grid_char)
+    }
+
+    pub fn render_with<F: Fn(&T) -> char>(&self, char_fn: F) -> GridRenderer<'_, T, F> {
+        GridRenderer::new(self, char_fn)
+    }
+}
+
+impl<'a, T, F: Fn(&T) -> char> GridRenderer<'a, T, F> {
+    fn new(grid: &'a Grid<T>, char_fn: F) -> Self {
+        Self {
+            grid,
+            char_fn,
+            axes: false,
+            highlights: HashMap::new(),
+            crop: Some(grid.bounds()).filter(|bounds| !bounds.is_empty()),
+        }
+    }
+
+    /// Labels the rows and columns with their coordinates
+    pub fn with_axes(mut self) -> Self {
+        self.axes = true;
+        self
+    }
+
+    /// Draws the points at the given world coordinates in a color. If a point is
+    /// highlighted more than once, the last color wins.
+    pub fn highlight<I: IntoIterator<Item=Point>>(mut self, points: I, color: Color) -> Self {
+        self.highlights.extend(points.into_iter().map(|p| (p, color)));
+        self
+    }
+
+    /// Only renders the part of the grid inside the rectangle, in world coordinates
+    pub fn crop(mut self, rect: Rect) -> Self {
+        self.crop = self.grid.bounds().intersection(&rect);
+        self
+    }
+}
+
+impl<'a, T, F: Fn(&T) -> char> fmt::Display for GridRenderer<'a, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rect = match self.crop {
+            Some(rect) => rect,
+            None => return Ok(()),
+        };
+
+        // Axes are labelled with world coordinates, so they may be negative
+        let x_labels = (rect.min.x..=rect.max.x).map(|x| x.to_string()).collect::<Vec<_>>();
+        let y_labels = (rect.min.y..=rect.max.y).map(|y| y.to_string()).collect::<Vec<_>>();
+        let label_width = y_labels.iter().map(String::len).max().unwrap();
+
+        if self.axes {
+            // Column labels are written vertically, one character per line, so that
+            // every label fits in a single column
+            let label_height = x_labels.iter().map(String::len).max().unwrap();
+
+            for i in 0..label_height {
+                write!(f, "{:1$} ", "", label_width)?;
+                for label in x_labels.iter() {
+                    let label = format!("{:>1$}", label, label_height);
+                    write!(f, "{}", label.as_bytes()[i] as char)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        for (y, label) in (rect.min.y..=rect.max.y).zip(y_labels.iter()) {
+            if self.axes {
+                write!(f, "{:>1$} ", label, label_width)?;
+            }
+
+            for x in rect.min.x..=rect.max.x {
+                let node = self.grid.at_point(Point::new(x, y)).unwrap();
+                let ch = (self.char_fn)(&*node);
+                match self.highlights.get(&node.point()) {
+                    Some(color) => write!(f, "\x1b[{}m{}\x1b[0m", color.ansi_code(), ch)?,
+                    None => write!(f, "{}", ch)?,
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod render_tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let rect = |x0, y0, x1, y1| Rect::new(Point::new(x0, y0), Point::new(x1, y1));
+        let grid = Grid::new((0..12).map(|y| (0..12).map(|x| x == y).collect()).collect());
+
+        assert_eq!(format!("{}", grid.render().crop(rect(0, 0, 2, 1))), "#..\n.#.\n");
+        assert_eq!(
+            format!("{}", grid.render().with_axes().crop(rect(9, 8, 11, 10))),
+            "    11\n   901\n 8 ...\n 9 #..\n10 .#.\n",
+        );
+        assert_eq!(
+            format!("{}", grid.render_with(|_| 'x').highlight([Point::new(1, 0)], Color::Red).crop(rect(0, 0, 1, 0))),
+            "x\x1b[31mx\x1b[0m\n",
+        );
+
+        let grid = Grid::new(vec![vec!['a', 'b'], vec!['c', 'd']]).with_origin(Point::new(-1, 9));
+        assert_eq!(format!("{}", grid.render().with_axes()), "   - \n   10\n 9 ab\n10 cd\n");
+        assert_eq!(format!("{}", grid.render().crop(rect(0, 10, 5, 20))), "d\n");
+        assert_eq!(format!("{}", grid.render().crop(rect(5, 5, 6, 6))), "");
+    }
+}
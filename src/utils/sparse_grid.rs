@@ -0,0 +1,160 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use crate::utils::{Point, Rect};
+
+/// Represents an unbounded 2D grid of elements of type T
+///
+/// Unlike Grid, which owns a dense rectangle of values, a SparseGrid only stores
+/// the cells that have been explicitly set. Every other cell reads as the default
+/// value given at construction. Coordinates may be negative, so simulations that
+/// grow in any direction don't need to shift their points into a fixed rectangle.
+///
+/// # Examples
+/// ```
+/// let mut grid = SparseGrid::new('.');
+/// grid.set(Point::new(-3, 2), '#');
+///
+/// assert_eq!(*grid.get(Point::new(-3, 2)), '#');
+/// assert_eq!(*grid.get(Point::new(100, 100)), '.');
+///
+/// for (point, value) in grid.neighbors(Point::new(-3, 1)) {
+///     // ...
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SparseGrid<T> {
+    items: HashMap<Point, T>,
+    default: T,
+    // The bounding box of all stored points. Removing a point may shrink the box,
+    // so removals only mark it as stale and the next call to bounds() recomputes it.
+    bounds: Cell<Option<Rect>>,
+    bounds_stale: Cell<bool>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            items: HashMap::new(),
+            default,
+            bounds: Cell::new(None),
+            bounds_stale: Cell::new(false),
+        }
+    }
+
+    pub fn get(&self, point: Point) -> &T {
+        self.items.get(&point).unwrap_or(&self.default)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> &mut T where T: Clone {
+        if !self.items.contains_key(&point) {
+            self.set(point, self.default.clone());
+        }
+
+        self.items.get_mut(&point).unwrap()
+    }
+
+    pub fn set(&mut self, point: Point, value: T) {
+        if !self.bounds_stale.get() {
+            let bounds = match self.bounds.get() {
+                None => Rect::new(point, point),
+                Some(bounds) => bounds.including(point),
+            };
+            self.bounds.set(Some(bounds));
+        }
+
+        self.items.insert(point, value);
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let value = self.items.remove(&point);
+        if value.is_some() {
+            self.bounds_stale.set(true);
+        }
+        value
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.items.contains_key(&point)
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// The number of explicitly stored cells
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the bounding box of every stored cell, or None if the grid is empty
+    pub fn bounds(&self) -> Option<Rect> {
+        if self.bounds_stale.get() {
+            self.bounds.set(Rect::from_points(self.items.keys().copied()));
+            self.bounds_stale.set(false);
+        }
+
+        self.bounds.get()
+    }
+
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, Point, T> {
+        self.items.iter()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item=Point> + '_ {
+        self.items.keys().copied()
+    }
+
+    /// Iterates over the four orthogonal neighbors of a point. Cells that have not
+    /// been set yield the default value.
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item=(Point, &T)> + '_ {
+        point.neighbors4().map(move |neighbor| (neighbor, self.get(neighbor)))
+    }
+
+    /// Iterates over all eight neighbors of a point, including diagonals
+    pub fn neighbors_diagonal(&self, point: Point) -> impl Iterator<Item=(Point, &T)> + '_ {
+        point.neighbors8().map(move |neighbor| (neighbor, self.get(neighbor)))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(bounds) = self.bounds() {
+            for point in bounds.points() {
+                write!(f, "{}", self.get(point))?;
+                if point.x == bounds.max.x {
+                    writeln!(f)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod sparse_grid_tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+
+        grid.set(Point::new(-2, 3), '#');
+        grid.set(Point::new(4, -1), '#');
+        grid.set(Point::new(0, 0), '#');
+        assert_eq!(grid.bounds(), Some(Rect::new(Point::new(-2, -1), Point::new(4, 3))));
+
+        grid.remove(Point::new(4, -1));
+        assert_eq!(grid.bounds(), Some(Rect::new(Point::new(-2, 0), Point::new(0, 3))));
+
+        assert_eq!(*grid.get(Point::new(100, -100)), '.');
+        assert_eq!(grid.neighbors(Point::new(-1, 0)).filter(|(_, v)| **v == '#').count(), 1);
+        assert_eq!(format!("{}", grid), "..#\n...\n...\n#..\n");
+    }
+}
@@ -0,0 +1,75 @@
+use crate::utils::{Point, Direction};
+
+/// Decides what happens when navigation moves off the edge of a Grid.
+///
+/// Every navigation method on GridNode consults the topology of its grid when a
+/// move would leave the grid (or the GridView the node belongs to). Grids are
+/// Bounded unless another topology is set with Grid::with_topology.
+///
+/// # Examples
+/// ```
+/// let grid = Grid::new(my_vector).with_topology(Torus);
+/// let node = grid.at(0, 0).unwrap();
+///
+/// // Wraps around to the last column
+/// let left_node = node.left().unwrap();
+/// ```
+pub trait Topology {
+    /// Maps a position outside of a width x height grid back onto the grid.
+    ///
+    /// `direction` is the direction of travel if the move was along a single axis.
+    /// Returns the position the move lands on along with the new direction of
+    /// travel, or None if the edge can't be crossed.
+    fn cross_edge(&self, position: Point, direction: Option<Direction>, width: usize, height: usize) -> Option<(Point, Option<Direction>)>;
+}
+
+/// Edges can't be crossed. This is the default topology of a Grid.
+#[derive(Debug, Copy, Clone)]
+pub struct Bounded;
+
+/// Each edge wraps around to the opposite edge
+#[derive(Debug, Copy, Clone)]
+pub struct Torus;
+
+/// Edges are crossed according to a user-provided function with the same
+/// signature as Topology::cross_edge. Useful for portals or folding a map onto
+/// a cube, where crossing an edge may also change the direction of travel.
+pub struct Custom<F> {
+    cross_edge_fn: F,
+}
+
+impl Topology for Bounded {
+    fn cross_edge(&self, _: Point, _: Option<Direction>, _: usize, _: usize) -> Option<(Point, Option<Direction>)> {
+        None
+    }
+}
+
+impl Topology for Torus {
+    fn cross_edge(&self, position: Point, direction: Option<Direction>, width: usize, height: usize) -> Option<(Point, Option<Direction>)> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let wrapped = Point::new(
+            position.x.rem_euclid(width as isize),
+            position.y.rem_euclid(height as isize),
+        );
+        Some((wrapped, direction))
+    }
+}
+
+impl<F> Custom<F>
+    where F: Fn(Point, Option<Direction>, usize, usize) -> Option<(Point, Option<Direction>)>
+{
+    pub fn new(cross_edge_fn: F) -> Self {
+        Self { cross_edge_fn }
+    }
+}
+
+impl<F> Topology for Custom<F>
+    where F: Fn(Point, Option<Direction>, usize, usize) -> Option<(Point, Option<Direction>)>
+{
+    fn cross_edge(&self, position: Point, direction: Option<Direction>, width: usize, height: usize) -> Option<(Point, Option<Direction>)> {
+        (self.cross_edge_fn)(position, direction, width, height)
+    }
+}
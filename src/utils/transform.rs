@@ -0,0 +1,146 @@
+use std::ops::Mul;
+use crate::utils::Point;
+
+/// A 2x2 integer matrix acting on Points
+///
+/// Like the rest of the utilities, y points down, so ROTATE_CW turns Right into
+/// Down. The eight orientations (rotations and reflections that map the grid onto
+/// itself) are enumerated by Transform2::all(), and can be applied to a Grid with
+/// Grid::transformed.
+///
+/// # Examples
+/// ```
+/// let t = Transform2::ROTATE_CW * Transform2::REFLECT_X;
+/// assert_eq!(t.apply(Point::new(1, 0)), Point::new(0, -1));
+///
+/// for orientation in Transform2::all() {
+///     let candidate = grid.transformed(orientation);
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Transform2 {
+    /// Row-major, so a point (x, y) maps to (m[0][0] * x + m[0][1] * y, m[1][0] * x + m[1][1] * y)
+    pub m: [[isize; 2]; 2],
+}
+
+impl Transform2 {
+    pub const IDENTITY: Transform2 = Transform2::new([[1, 0], [0, 1]]);
+    pub const ROTATE_CW: Transform2 = Transform2::new([[0, -1], [1, 0]]);
+    pub const ROTATE_180: Transform2 = Transform2::new([[-1, 0], [0, -1]]);
+    pub const ROTATE_CCW: Transform2 = Transform2::new([[0, 1], [-1, 0]]);
+    /// Negates x, mirroring left-to-right
+    pub const REFLECT_X: Transform2 = Transform2::new([[-1, 0], [0, 1]]);
+    /// Negates y, mirroring top-to-bottom
+    pub const REFLECT_Y: Transform2 = Transform2::new([[1, 0], [0, -1]]);
+    /// Swaps x and y
+    pub const TRANSPOSE: Transform2 = Transform2::new([[0, 1], [1, 0]]);
+
+    pub const fn new(m: [[isize; 2]; 2]) -> Self {
+        Self { m }
+    }
+
+    /// Iterates over the eight orientations: the four rotations, followed by the
+    /// four rotations of the left-to-right mirror image
+    pub fn all() -> impl Iterator<Item=Transform2> {
+        [Transform2::IDENTITY, Transform2::REFLECT_X].into_iter().flat_map(|reflection| {
+            [Transform2::IDENTITY, Transform2::ROTATE_CW, Transform2::ROTATE_180, Transform2::ROTATE_CCW]
+                .into_iter()
+                .map(move |rotation| rotation * reflection)
+        })
+    }
+
+    pub fn apply(&self, point: Point) -> Point {
+        Point::new(
+            self.m[0][0] * point.x + self.m[0][1] * point.y,
+            self.m[1][0] * point.x + self.m[1][1] * point.y,
+        )
+    }
+
+    /// Returns the transform that applies self and then other
+    pub fn then(&self, other: Transform2) -> Transform2 {
+        other * *self
+    }
+
+    pub fn determinant(&self) -> isize {
+        self.m[0][0] * self.m[1][1] - self.m[0][1] * self.m[1][0]
+    }
+
+    /// Returns the inverse transform, or None if it doesn't have integer entries.
+    /// Every orientation has an inverse.
+    pub fn inverse(&self) -> Option<Transform2> {
+        let det = self.determinant();
+        if det != 1 && det != -1 {
+            return None;
+        }
+
+        Some(Transform2::new([
+            [self.m[1][1] * det, -self.m[0][1] * det],
+            [-self.m[1][0] * det, self.m[0][0] * det],
+        ]))
+    }
+
+    /// Whether this is one of the eight orientations returned by all()
+    pub fn is_orientation(&self) -> bool {
+        let [[a, b], [c, d]] = self.m;
+        (a == 0 && d == 0 && b.abs() == 1 && c.abs() == 1) || (b == 0 && c == 0 && a.abs() == 1 && d.abs() == 1)
+    }
+}
+
+impl Mul for Transform2 {
+    type Output = Transform2;
+
+    /// Matrix product, so (a * b).apply(p) == a.apply(b.apply(p))
+    fn mul(self, rhs: Transform2) -> Transform2 {
+        let mut m = [[0; 2]; 2];
+        for (row, m_row) in m.iter_mut().enumerate() {
+            for (col, value) in m_row.iter_mut().enumerate() {
+                *value = self.m[row][0] * rhs.m[0][col] + self.m[row][1] * rhs.m[1][col];
+            }
+        }
+        Transform2::new(m)
+    }
+}
+
+impl Mul<Point> for Transform2 {
+    type Output = Point;
+
+    fn mul(self, rhs: Point) -> Point {
+        self.apply(rhs)
+    }
+}
+
+#[cfg(test)]
+mod transform_tests {
+    use super::*;
+    use crate::utils::Grid;
+
+    #[test]
+    fn test_transform() {
+        let p = Point::new(2, 1);
+        assert_eq!(Transform2::ROTATE_CW * p, Point::new(-1, 2));
+        assert_eq!(Transform2::ROTATE_CW * Transform2::ROTATE_CW, Transform2::ROTATE_180);
+        assert_eq!(Transform2::ROTATE_CW.then(Transform2::ROTATE_CCW), Transform2::IDENTITY);
+        assert_eq!(Transform2::REFLECT_X.then(Transform2::ROTATE_CW), Transform2::ROTATE_CW * Transform2::REFLECT_X);
+        assert_eq!(Transform2::ROTATE_CW.inverse(), Some(Transform2::ROTATE_CCW));
+        assert_eq!(Transform2::new([[2, 0], [0, 1]]).inverse(), None);
+
+        let all = Transform2::all().collect::<Vec<_>>();
+        assert_eq!(all.len(), 8);
+        assert!(all.contains(&Transform2::TRANSPOSE) && all.contains(&Transform2::REFLECT_Y));
+        assert!(all.iter().all(|t| t.is_orientation()));
+        assert!(all.iter().all(|t| all.iter().filter(|u| *u == t).count() == 1));
+    }
+
+    #[test]
+    fn test_grid_transform() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert!(grid.transformed(Transform2::IDENTITY) == grid);
+        assert!(grid.transformed(Transform2::ROTATE_CW) == grid.rotate_cw());
+        assert!(grid.transformed(Transform2::ROTATE_CCW) == grid.rotate_ccw());
+        assert!(grid.transformed(Transform2::REFLECT_X) == grid.flip_horizontal());
+        assert!(grid.transformed(Transform2::REFLECT_Y) == grid.flip_vertical());
+        assert!(grid.transformed(Transform2::TRANSPOSE) == grid.transpose());
+    }
+}